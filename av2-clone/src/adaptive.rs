@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use crate::{strategy::Round, strategy::Strategy, RPSThrow};
+
+/// Picks the throw with the highest count, preferring the earlier one in `RPSThrow::ALL` on ties.
+fn most_likely(counts: &[u32; 3]) -> Option<RPSThrow> {
+    let mut best: Option<(RPSThrow, u32)> = None;
+    for (throw, &count) in RPSThrow::ALL.iter().zip(counts) {
+        if count > best.map_or(0, |(_, best_count)| best_count) {
+            best = Some((*throw, count));
+        }
+    }
+    best.map(|(throw, _)| throw)
+}
+
+/// Counts every opponent throw and counters the most frequent one.
+#[derive(Default)]
+pub struct FrequencyCounter {
+    counts: [u32; 3],
+    seen: usize,
+}
+
+impl FrequencyCounter {
+    fn update(&mut self, history: &[Round]) {
+        for (_, other_throw) in &history[self.seen..] {
+            self.counts[other_throw.index()] += 1;
+        }
+        self.seen = history.len();
+    }
+
+    pub fn predict(&self) -> Option<RPSThrow> {
+        most_likely(&self.counts)
+    }
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency".to_string()
+    }
+
+    fn next_throw(&mut self, history: &[Round]) -> RPSThrow {
+        self.update(history);
+        self.predict()
+            .map(|throw| throw.beaten_by())
+            .unwrap_or(RPSThrow::Rock)
+    }
+
+    fn reset(&mut self) {
+        *self = FrequencyCounter::default();
+    }
+}
+
+/// Order-k Markov chain over the opponent's throws.
+///
+/// Learns which throw followed each sequence of `order` opponent throws and counters the most
+/// likely successor of the current sequence, falling back to plain frequencies for unseen ones.
+pub struct Markov {
+    order: usize,
+    transitions: HashMap<Vec<RPSThrow>, [u32; 3]>,
+    fallback: FrequencyCounter,
+    opponent_throws: Vec<RPSThrow>,
+}
+
+impl Markov {
+    pub fn new(order: usize) -> Self {
+        assert!(order > 0, "Markov order should be at least 1");
+        Markov {
+            order,
+            transitions: HashMap::new(),
+            fallback: FrequencyCounter::default(),
+            opponent_throws: vec![],
+        }
+    }
+
+    fn update(&mut self, history: &[Round]) {
+        for (_, other_throw) in &history[self.opponent_throws.len()..] {
+            let len = self.opponent_throws.len();
+            if len >= self.order {
+                let context = self.opponent_throws[len - self.order..].to_vec();
+                self.transitions.entry(context).or_insert([0; 3])[other_throw.index()] += 1;
+            }
+            self.opponent_throws.push(*other_throw);
+        }
+        self.fallback.update(history);
+    }
+
+    pub fn predict(&self) -> Option<RPSThrow> {
+        let len = self.opponent_throws.len();
+        if len < self.order {
+            return self.fallback.predict();
+        }
+        self.transitions
+            .get(&self.opponent_throws[len - self.order..])
+            .and_then(most_likely)
+            .or_else(|| self.fallback.predict())
+    }
+}
+
+impl Strategy for Markov {
+    fn name(&self) -> String {
+        format!("markov-{}", self.order)
+    }
+
+    fn next_throw(&mut self, history: &[Round]) -> RPSThrow {
+        self.update(history);
+        self.predict()
+            .map(|throw| throw.beaten_by())
+            .unwrap_or(RPSThrow::Rock)
+    }
+
+    fn reset(&mut self) {
+        *self = Markov::new(self.order);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adaptive::{FrequencyCounter, Markov};
+    use crate::strategy::{Pattern, Strategy};
+    use crate::tournament::{play_match, round_summary};
+    use crate::RPSThrow;
+
+    #[test]
+    fn test_frequency_predicts_most_common() {
+        let mut frequency = FrequencyCounter::default();
+        let history = [
+            (RPSThrow::Rock, RPSThrow::Scissors),
+            (RPSThrow::Rock, RPSThrow::Paper),
+            (RPSThrow::Rock, RPSThrow::Scissors),
+        ];
+        assert_eq!(RPSThrow::Rock, frequency.next_throw(&history));
+        assert_eq!(Some(RPSThrow::Scissors), frequency.predict());
+    }
+
+    #[test]
+    fn test_markov_learns_pattern() {
+        let mut markov = Markov::new(2);
+        let mut pattern = Pattern::new(vec![RPSThrow::Rock, RPSThrow::Rock, RPSThrow::Paper]);
+        let summary = round_summary(&play_match(&mut markov, &mut pattern, 300));
+
+        // only the first few rounds are needed to learn the pattern
+        assert!(summary.wins >= 290, "{:?}", summary);
+    }
+
+    #[test]
+    fn test_reset_forgets_history() {
+        let mut markov = Markov::new(1);
+        markov.next_throw(&[(RPSThrow::Rock, RPSThrow::Paper)]);
+        markov.reset();
+        assert_eq!(None, markov.predict());
+    }
+}
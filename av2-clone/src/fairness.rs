@@ -0,0 +1,182 @@
+use crate::RPSThrow;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FairnessReport {
+    pub counts: [u64; 3],
+    pub chi_square: f64,
+    /// Probability of a chi-square statistic at least this large for a fair generator.
+    pub chi_square_p: f64,
+    pub runs: u64,
+    pub expected_runs: f64,
+    pub runs_z: f64,
+    /// Two-sided probability of this many runs or a more extreme number for independent throws.
+    pub runs_p: f64,
+}
+
+impl FairnessReport {
+    pub fn is_fair(&self, alpha: f64) -> bool {
+        self.chi_square_p >= alpha && self.runs_p >= alpha
+    }
+}
+
+/// Complementary error function, accurate to about 1.2e-7 (Numerical Recipes' `erfcc`).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let result = t * polynomial.exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+pub fn draw_throws<F: FnMut() -> RPSThrow>(n: usize, mut generator: F) -> Vec<RPSThrow> {
+    (0..n).map(|_| generator()).collect()
+}
+
+/// Runs a chi-square goodness-of-fit test against the uniform distribution and a runs test
+/// for serial correlation (a run is a maximal block of identical consecutive throws).
+pub fn check_fairness(throws: &[RPSThrow]) -> FairnessReport {
+    assert!(throws.len() > 1, "Should have at least two throws to test");
+
+    let mut counts = [0u64; 3];
+    for throw in throws {
+        counts[throw.index()] += 1;
+    }
+    let n = throws.len() as f64;
+
+    let expected = n / 3.0;
+    let chi_square: f64 = counts
+        .iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum();
+    // the survival function of a chi-square distribution with 2 degrees of freedom
+    let chi_square_p = (-chi_square / 2.0).exp();
+
+    let runs = 1 + throws.windows(2).filter(|pair| pair[0] != pair[1]).count() as u64;
+    let sum_squares: f64 = counts.iter().map(|&count| (count as f64).powi(2)).sum();
+    let sum_cubes: f64 = counts.iter().map(|&count| (count as f64).powi(3)).sum();
+    let expected_runs = (n * (n + 1.0) - sum_squares) / n;
+    let variance = (sum_squares * (sum_squares + n * (n + 1.0)) - 2.0 * n * sum_cubes - n.powi(3))
+        / (n * n * (n - 1.0));
+    let (runs_z, runs_p) = if variance > 0.0 {
+        let z = (runs as f64 - expected_runs) / variance.sqrt();
+        (z, erfc(z.abs() / std::f64::consts::SQRT_2))
+    } else {
+        // every throw was the same, which is as unfair as it gets
+        (f64::INFINITY, 0.0)
+    };
+
+    FairnessReport {
+        counts,
+        chi_square,
+        chi_square_p,
+        runs,
+        expected_runs,
+        runs_z,
+        runs_p,
+    }
+}
+
+pub fn print_report(report: &FairnessReport, alpha: f64) {
+    let total: u64 = report.counts.iter().sum();
+    for (throw, count) in RPSThrow::ALL.iter().zip(report.counts) {
+        println!(
+            "{:<10} {:>10} {:>7.3}%",
+            throw.name(),
+            count,
+            100.0 * count as f64 / total as f64
+        );
+    }
+    println!(
+        "chi-square {:.4} (2 df), p = {:.4}",
+        report.chi_square, report.chi_square_p
+    );
+    println!(
+        "runs {} (expected {:.1}), z = {:.4}, p = {:.4}",
+        report.runs, report.expected_runs, report.runs_z, report.runs_p
+    );
+    if report.is_fair(alpha) {
+        println!("No evidence of bias at alpha = {}", alpha);
+    } else {
+        println!("The generator looks biased at alpha = {}", alpha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::fairness::{check_fairness, draw_throws, erfc};
+    use crate::{get_random_throw, get_random_throw_from, RPSThrow};
+
+    const ALPHA: f64 = 1e-6;
+
+    fn assert_fair<F: FnMut() -> RPSThrow>(generator: F) {
+        let report = check_fairness(&draw_throws(30_000, generator));
+        assert!(report.is_fair(ALPHA), "{:?}", report);
+    }
+
+    #[test]
+    fn test_erfc() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-6);
+        assert!((erfc(1.0) - 0.157299).abs() < 1e-6);
+        assert!((erfc(-1.0) - 1.842701).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_get_random_throw_is_fair() {
+        assert_fair(get_random_throw);
+    }
+
+    #[test]
+    fn test_seeded_throws_are_fair() {
+        let mut rng = StdRng::seed_from_u64(2022);
+        assert_fair(|| get_random_throw_from(&mut rng));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_biased_generator_is_rejected() {
+        let mut rng = StdRng::seed_from_u64(2022);
+        // a slight preference for rock: 35% instead of 33.3%
+        assert_fair(|| match rng.gen_range(0..100) {
+            0..=34 => RPSThrow::Rock,
+            35..=67 => RPSThrow::Paper,
+            _ => RPSThrow::Scissors,
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_correlated_generator_is_rejected() {
+        let mut rng = StdRng::seed_from_u64(2022);
+        let mut last = RPSThrow::Rock;
+        // uniform overall, but repeats the previous throw too often
+        assert_fair(|| {
+            if rng.gen_bool(0.1) {
+                last
+            } else {
+                last = get_random_throw_from(&mut rng);
+                last
+            }
+        });
+    }
+
+    #[test]
+    fn test_constant_generator() {
+        let report = check_fairness(&[RPSThrow::Paper; 10]);
+        assert_eq!(1, report.runs);
+        assert_eq!(0.0, report.runs_p);
+        assert!(!report.is_fair(0.05));
+    }
+}
@@ -0,0 +1,159 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    get_random_throw, get_rps_state, scoring::ScoringScheme, strategy::Round, strategy::Strategy,
+    RPSState, RPSThrow,
+};
+
+pub enum Command {
+    Throw(RPSThrow),
+    Quit,
+}
+
+pub fn read_command<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> io::Result<Command> {
+    loop {
+        write!(output, "Your throw (rock/paper/scissors, q to quit): ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(Command::Quit);
+        }
+        match line.trim().to_lowercase().as_str() {
+            "q" | "quit" | "exit" => return Ok(Command::Quit),
+            other => match other.parse::<RPSThrow>() {
+                Ok(throw) => return Ok(Command::Throw(throw)),
+                Err(err) => writeln!(output, "{}, try again", err)?,
+            },
+        }
+    }
+}
+
+/// Plays a best-of-`best_of` game against the computer, reading throws from `input`.
+///
+/// Draws do not count towards the rounds needed to win. Without a `computer` strategy the
+/// computer plays `get_random_throw`. Returns the rounds played from the player's point of view.
+pub fn play_interactive<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    best_of: u32,
+    mut computer: Option<&mut dyn Strategy>,
+    scheme: ScoringScheme,
+) -> io::Result<Vec<Round>> {
+    let needed_wins = best_of / 2 + 1;
+    let mut history: Vec<Round> = vec![];
+    let mut computer_history: Vec<Round> = vec![];
+    let (mut wins, mut draws, mut losses) = (0, 0, 0);
+    let (mut points, mut computer_points) = (0, 0);
+
+    if let Some(computer) = computer.as_mut() {
+        computer.reset();
+    }
+    writeln!(
+        output,
+        "Best of {}: first to {} wins, {:?} scoring",
+        best_of, needed_wins, scheme
+    )?;
+
+    while wins < needed_wins && losses < needed_wins {
+        let throw = match read_command(input, output)? {
+            Command::Throw(throw) => throw,
+            Command::Quit => {
+                writeln!(output, "Game abandoned")?;
+                break;
+            }
+        };
+        let computer_throw = match computer.as_mut() {
+            Some(computer) => computer.next_throw(&computer_history),
+            None => get_random_throw(),
+        };
+        history.push((throw, computer_throw));
+        computer_history.push((computer_throw, throw));
+
+        let state = get_rps_state(&throw, &computer_throw);
+        let score = scheme.score(&throw, &computer_throw);
+        points += score;
+        computer_points += scheme.score(&computer_throw, &throw);
+        match state {
+            RPSState::Win => wins += 1,
+            RPSState::Draw => draws += 1,
+            RPSState::Loss => losses += 1,
+        }
+        writeln!(
+            output,
+            "Round {}: {:?} vs {:?} => {:?} ({:+})",
+            history.len(),
+            throw,
+            computer_throw,
+            state,
+            score
+        )?;
+    }
+
+    writeln!(
+        output,
+        "{:<10} {:>4} {:>4} {:>4} {:>8}",
+        "", "W", "D", "L", "points"
+    )?;
+    writeln!(
+        output,
+        "{:<10} {:>4} {:>4} {:>4} {:>8}",
+        "you", wins, draws, losses, points
+    )?;
+    writeln!(
+        output,
+        "{:<10} {:>4} {:>4} {:>4} {:>8}",
+        "computer", losses, draws, wins, computer_points
+    )?;
+    if wins >= needed_wins {
+        writeln!(output, "You win the game!")?;
+    } else if losses >= needed_wins {
+        writeln!(output, "The computer wins the game.")?;
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interactive::play_interactive;
+    use crate::scoring::ScoringScheme;
+    use crate::strategy::Constant;
+    use crate::RPSThrow;
+
+    fn play(input: &str, best_of: u32) -> (Vec<(RPSThrow, RPSThrow)>, String) {
+        let mut computer = Constant(RPSThrow::Rock);
+        let mut output = vec![];
+        let history = play_interactive(
+            &mut input.as_bytes(),
+            &mut output,
+            best_of,
+            Some(&mut computer),
+            ScoringScheme::Football,
+        )
+        .unwrap();
+        (history, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_best_of_three_stops_after_two_wins() {
+        let (history, output) = play("paper\nrock\np\npaper\n", 3);
+
+        assert_eq!(3, history.len());
+        assert!(output.contains("You win the game!"));
+    }
+
+    #[test]
+    fn test_invalid_input_is_retried() {
+        let (history, output) = play("lizard\n\nscissors\nq\n", 3);
+
+        assert_eq!(vec![(RPSThrow::Scissors, RPSThrow::Rock)], history);
+        assert!(output.contains("unknown throw \"lizard\", try again"));
+        assert!(output.contains("Game abandoned"));
+    }
+
+    #[test]
+    fn test_end_of_input_quits() {
+        let (history, _) = play("", 5);
+        assert!(history.is_empty());
+    }
+}
@@ -1,4 +1,24 @@
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+mod adaptive;
+mod fairness;
+mod interactive;
+mod montecarlo;
+mod multiplayer;
+mod network;
+mod record;
+mod scoring;
+mod strategy;
+mod tournament;
+
+use std::{
+    env, fs,
+    io::{self, BufReader, BufWriter, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 enum RPSThrow {
     Rock,
     Paper,
@@ -10,6 +30,68 @@ enum RPSState {
     Loss,
     Draw,
 }
+#[derive(Debug)]
+enum ParseThrowError {
+    Unknown(String),
+}
+
+impl RPSThrow {
+    const ALL: [RPSThrow; 3] = [RPSThrow::Rock, RPSThrow::Paper, RPSThrow::Scissors];
+
+    fn name(&self) -> &'static str {
+        match self {
+            RPSThrow::Rock => "rock",
+            RPSThrow::Paper => "paper",
+            RPSThrow::Scissors => "scissors",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            RPSThrow::Rock => 0,
+            RPSThrow::Paper => 1,
+            RPSThrow::Scissors => 2,
+        }
+    }
+
+    /// Points for the shape itself under the strategy-guide scoring.
+    fn shape_value(&self) -> i32 {
+        match self {
+            RPSThrow::Rock => 1,
+            RPSThrow::Paper => 2,
+            RPSThrow::Scissors => 3,
+        }
+    }
+
+    /// The throw that wins against this one.
+    fn beaten_by(&self) -> RPSThrow {
+        match self {
+            RPSThrow::Rock => RPSThrow::Paper,
+            RPSThrow::Paper => RPSThrow::Scissors,
+            RPSThrow::Scissors => RPSThrow::Rock,
+        }
+    }
+}
+
+impl FromStr for RPSThrow {
+    type Err = ParseThrowError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rock" | "r" => Ok(RPSThrow::Rock),
+            "paper" | "p" => Ok(RPSThrow::Paper),
+            "scissors" | "s" => Ok(RPSThrow::Scissors),
+            _ => Err(ParseThrowError::Unknown(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseThrowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseThrowError::Unknown(s) => write!(f, "unknown throw {:?}", s),
+        }
+    }
+}
 
 // this is a text
 fn get_rps_state(throw: &RPSThrow, other_throw: &RPSThrow) -> RPSState {
@@ -30,18 +112,399 @@ fn get_rps_state_value(state: &RPSState) -> i8 {
     }
 }
 
-fn get_random_throw() -> RPSThrow {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let throw = match rng.gen_range(0..3) {
+fn get_random_throw_from<R: Rng>(rng: &mut R) -> RPSThrow {
+    match rng.gen_range(0..3) {
         0 => RPSThrow::Rock,
         1 => RPSThrow::Paper,
         _ => RPSThrow::Scissors,
+    }
+}
+
+fn get_random_throw() -> RPSThrow {
+    get_random_throw_from(&mut rand::thread_rng())
+}
+
+/// Returns the value following `--name` on the command line, if present.
+fn get_option<T>(args: &[String], name: &str) -> Option<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|err| panic!("Invalid value {:?} for {}: {}", value, name, err))
+        })
+}
+
+/// Opens the file given with `--record`, if any.
+fn open_log(args: &[String]) -> Option<BufWriter<fs::File>> {
+    get_option::<String>(args, "--record").map(|file_path| {
+        BufWriter::new(
+            fs::File::create(file_path).expect("Should have been able to create the log"),
+        )
+    })
+}
+
+fn write_log(log: &mut Option<BufWriter<fs::File>>, game: &record::GameRecord) {
+    if let Some(log) = log.as_mut() {
+        game.write_to(log)
+            .and_then(|_| log.flush())
+            .expect("Should have been able to write the log");
+    }
+}
+
+/// Returns the arguments that are neither options nor option values.
+fn get_positional(args: &[String]) -> Vec<&str> {
+    let mut positional = vec![];
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg.starts_with("--") {
+            iter.next();
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    positional
+}
+
+fn run_tournament(args: &[String]) {
+    let rounds: usize = get_option(args, "--rounds").unwrap_or(1000);
+    let seed: u64 = get_option(args, "--seed").unwrap_or_else(|| rand::thread_rng().gen());
+    let scheme: scoring::ScoringScheme = get_option(args, "--scoring").unwrap_or_default();
+
+    let names = match get_positional(args) {
+        names if names.is_empty() => strategy::STRATEGY_NAMES.to_vec(),
+        names => names,
     };
-    return throw;
+    let mut strategies: Vec<Box<dyn strategy::Strategy>> = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            strategy::strategy_from_name(name, strategy::mix_seed(seed, index as u64))
+                .unwrap_or_else(|| panic!("Unknown strategy {:?}", name))
+        })
+        .collect();
+
+    println!(
+        "{} rounds per match, seed {}, {:?} scoring",
+        rounds, seed, scheme
+    );
+    let mut log = open_log(args);
+    let mut match_number = 0;
+    let standings =
+        tournament::run_tournament(&mut strategies, rounds, scheme, &mut |a, b, history| {
+            match_number += 1;
+            let game = record::GameRecord {
+                label: format!("tournament-match-{}", match_number),
+                players: (a.to_string(), b.to_string()),
+                seed: Some(seed),
+                scheme,
+                rounds: history.to_vec(),
+            };
+            write_log(&mut log, &game);
+        });
+    tournament::print_standings(&standings);
 }
 
-fn main() {
+fn run_benchmark(args: &[String]) {
+    let rounds: usize = get_option(args, "--rounds").unwrap_or(1000);
+    let seed: u64 = get_option(args, "--seed").unwrap_or_else(|| rand::thread_rng().gen());
+
+    let mut players: Vec<Box<dyn strategy::Strategy>> = vec![
+        Box::new(strategy::Random::new(strategy::mix_seed(seed, 0))),
+        Box::new(adaptive::FrequencyCounter::default()),
+        Box::new(adaptive::Markov::new(1)),
+        Box::new(adaptive::Markov::new(2)),
+        Box::new(adaptive::Markov::new(3)),
+    ];
+    let mut opponents: Vec<Box<dyn strategy::Strategy>> = vec![
+        Box::new(strategy::Biased::new(
+            [5, 3, 2],
+            strategy::mix_seed(seed, 1),
+        )),
+        Box::new(strategy::Biased::new(
+            [1, 1, 2],
+            strategy::mix_seed(seed, 2),
+        )),
+        Box::new(strategy::Cycle),
+        Box::new(strategy::Pattern::new(vec![
+            RPSThrow::Rock,
+            RPSThrow::Rock,
+            RPSThrow::Paper,
+            RPSThrow::Scissors,
+            RPSThrow::Scissors,
+        ])),
+        Box::new(strategy::BeatLast),
+        Box::new(strategy::Random::new(strategy::mix_seed(seed, 3))),
+    ];
+
+    println!("{} rounds per match, seed {}", rounds, seed);
+    println!(
+        "{:<12} {:<20} {:>7} {:>7} {:>7}",
+        "player", "opponent", "win%", "draw%", "loss%"
+    );
+    for player in players.iter_mut() {
+        for opponent in opponents.iter_mut() {
+            let history = tournament::play_match(player.as_mut(), opponent.as_mut(), rounds);
+            let summary = tournament::round_summary(&history);
+            println!(
+                "{:<12} {:<20} {:>6.1}% {:>6.1}% {:>6.1}%",
+                player.name(),
+                opponent.name(),
+                summary.percent(summary.wins),
+                summary.percent(summary.draws),
+                summary.percent(summary.losses)
+            );
+        }
+    }
+}
+
+fn run_montecarlo(args: &[String]) {
+    let names = get_positional(args);
+    let (a, b) = match names[..] {
+        [a, b] => (a, b),
+        _ => panic!("Should have exactly two strategies, got {:?}", names),
+    };
+    for name in [a, b] {
+        if strategy::strategy_from_name(name, 0).is_none() {
+            panic!("Unknown strategy {:?}", name);
+        }
+    }
+    let config = montecarlo::SimulationConfig {
+        samples: get_option(args, "--samples").unwrap_or(1000),
+        rounds: get_option(args, "--rounds").unwrap_or(100),
+        seed: get_option(args, "--seed").unwrap_or_else(|| rand::thread_rng().gen()),
+        threads: get_option(args, "--threads").unwrap_or(1),
+        scheme: get_option(args, "--scoring").unwrap_or_default(),
+    };
+
+    println!(
+        "{} matches of {} rounds, seed {}, {:?} scoring, {} threads",
+        config.samples, config.rounds, config.seed, config.scheme, config.threads
+    );
+    let (estimate_a, estimate_b) = montecarlo::simulate(a, b, &config);
+    println!(
+        "{:<12} {:>10} {:>10} {:>22}",
+        "strategy", "mean", "std dev", "95% CI"
+    );
+    for (name, estimate) in [(a, estimate_a), (b, estimate_b)] {
+        println!(
+            "{:<12} {:>10.4} {:>10.4}     [{:.4}, {:.4}]",
+            name, estimate.mean, estimate.std_dev, estimate.ci_low, estimate.ci_high
+        );
+    }
+}
+
+fn run_elimination(args: &[String]) {
+    let max_players: usize = get_option(args, "--players").unwrap_or(10);
+    let games: usize = get_option(args, "--games").unwrap_or(10_000);
+    if !(2..=multiplayer::MAX_PLAYERS).contains(&max_players) {
+        panic!(
+            "Should have between 2 and {} players, got {}",
+            multiplayer::MAX_PLAYERS,
+            max_players
+        );
+    }
+    if games == 0 {
+        panic!("Should simulate at least one game per player count");
+    }
+    let seed: u64 = get_option(args, "--seed").unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    println!("{} games per player count, seed {}", games, seed);
+    println!(
+        "{:>8} {:>10} {:>10} {:>10} {:>6}",
+        "players", "expected", "mean", "std dev", "max"
+    );
+    for players in 2..=max_players {
+        let stats = multiplayer::simulate_elimination(players, games, &mut rng);
+        println!(
+            "{:>8} {:>10.4} {:>10.4} {:>10.4} {:>6}",
+            players,
+            multiplayer::expected_rounds(players),
+            stats.mean,
+            stats.std_dev,
+            stats.max
+        );
+    }
+}
+
+fn run_teams(args: &[String]) {
+    let rounds: usize = get_option(args, "--rounds").unwrap_or(1000);
+    let seed: u64 = get_option(args, "--seed").unwrap_or_else(|| rand::thread_rng().gen());
+    let teams: Vec<Vec<Box<dyn strategy::Strategy>>> = get_positional(args)
+        .iter()
+        .enumerate()
+        .map(|(team_index, team)| {
+            team.split(',')
+                .enumerate()
+                .map(|(member_index, name)| {
+                    let team_seed = strategy::mix_seed(seed, team_index as u64);
+                    let member_seed = strategy::mix_seed(team_seed, member_index as u64);
+                    strategy::strategy_from_name(name, member_seed)
+                        .unwrap_or_else(|| panic!("Unknown strategy {:?}", name))
+                })
+                .collect()
+        })
+        .collect();
+    let [mut team, mut other_team]: [Vec<Box<dyn strategy::Strategy>>; 2] = teams
+        .try_into()
+        .unwrap_or_else(|_| panic!("Should have exactly two comma-separated teams"));
+
+    let (wins, draws, losses) = multiplayer::play_team_match(&mut team, &mut other_team, rounds);
+    println!("{} rounds, seed {}", rounds, seed);
+    println!(
+        "team 1 wins {}, draws {}, team 2 wins {}",
+        wins, draws, losses
+    );
+}
+
+fn run_fairness(args: &[String]) {
+    let draws: usize = get_option(args, "--draws").unwrap_or(100_000);
+    let alpha: f64 = get_option(args, "--alpha").unwrap_or(0.01);
+
+    let throws = fairness::draw_throws(draws, get_random_throw);
+    fairness::print_report(&fairness::check_fairness(&throws), alpha);
+}
+
+fn run_interactive(args: &[String]) {
+    let best_of: u32 = get_option(args, "--best-of").unwrap_or(3);
+    let seed: u64 = get_option(args, "--seed").unwrap_or_else(|| rand::thread_rng().gen());
+    let scheme: scoring::ScoringScheme = get_option(args, "--scoring").unwrap_or_default();
+    let mut computer = get_option::<String>(args, "--opponent").map(|name| {
+        strategy::strategy_from_name(&name, strategy::mix_seed(seed, 1))
+            .unwrap_or_else(|| panic!("Unknown strategy {:?}", name))
+    });
+    let mut log = open_log(args);
+
+    let history = interactive::play_interactive(
+        &mut io::stdin().lock(),
+        &mut io::stdout(),
+        best_of,
+        computer
+            .as_mut()
+            .map(|computer| computer.as_mut() as &mut dyn strategy::Strategy),
+        scheme,
+    )
+    .expect("Should have been able to talk to the terminal");
+
+    let game = record::GameRecord {
+        label: "interactive".to_string(),
+        players: (
+            "you".to_string(),
+            computer
+                .as_ref()
+                .map_or("random".to_string(), |computer| computer.name()),
+        ),
+        seed: computer.as_ref().map(|_| seed),
+        scheme,
+        rounds: history,
+    };
+    write_log(&mut log, &game);
+}
+
+fn run_replay(args: &[String]) {
+    let file_path = *get_positional(args)
+        .first()
+        .expect("Should have a log file");
+    let content = fs::read_to_string(file_path).expect("Should have been able to read the file");
+
+    let (games, problems) = record::replay(&content);
+    for game in &games {
+        println!(
+            "{} {} vs {}: {} rounds",
+            game.label,
+            game.players.0,
+            game.players.1,
+            game.rounds.len()
+        );
+    }
+    for problem in &problems {
+        println!("line {}: {}", problem.line, problem.message);
+    }
+    println!(
+        "{} games, {} rounds, {} inconsistencies",
+        games.len(),
+        games.iter().map(|game| game.rounds.len()).sum::<usize>(),
+        problems.len()
+    );
+}
+
+fn run_convert(args: &[String]) {
+    let file_path = *get_positional(args)
+        .first()
+        .expect("Should have a log file");
+    let content = fs::read_to_string(file_path).expect("Should have been able to read the file");
+    let outcome = match get_option::<String>(args, "--column").as_deref() {
+        None | Some("throw") => false,
+        Some("outcome") => true,
+        Some(column) => panic!("Unknown column {:?}, expected throw or outcome", column),
+    };
+
+    let (games, problems) = record::replay(&content);
+    for problem in &problems {
+        eprintln!("line {}: {}", problem.line, problem.message);
+    }
+    print!("{}", record::to_strategy_guide(&games, outcome));
+}
+
+fn run_network(args: &[String], host: bool) {
+    let port: u16 = get_option(args, "--port").unwrap_or(7878);
+    let address = ("127.0.0.1", port);
+
+    let stream = if host {
+        let listener = TcpListener::bind(address).expect("Should have been able to listen");
+        println!("Waiting for the other player on port {}...", port);
+        listener.accept().expect("Should have accepted a player").0
+    } else {
+        TcpStream::connect(address).expect("Should have been able to connect")
+    };
+    let mut reader = BufReader::new(stream.try_clone().expect("Should have cloned the stream"));
+    let mut writer = stream;
+
+    let best_of = if host {
+        let best_of: u32 = get_option(args, "--best-of").unwrap_or(3);
+        network::send_best_of(&mut writer, best_of).expect("Should have sent the game rules");
+        best_of
+    } else {
+        network::read_best_of(&mut reader)
+            .unwrap_or_else(|err| panic!("Could not join the game: {}", err))
+    };
+
+    let scheme: scoring::ScoringScheme = get_option(args, "--scoring").unwrap_or_default();
+    let mut log = open_log(args);
+
+    match network::play_network(
+        &mut reader,
+        &mut writer,
+        &mut io::stdin().lock(),
+        &mut io::stdout(),
+        best_of,
+    ) {
+        Ok(history) => {
+            let (me, other) = if host {
+                ("host", "join")
+            } else {
+                ("join", "host")
+            };
+            let game = record::GameRecord {
+                label: format!("network-{}", me),
+                players: (me.to_string(), other.to_string()),
+                seed: None,
+                scheme,
+                rounds: history,
+            };
+            write_log(&mut log, &game);
+        }
+        Err(err) => eprintln!("Game aborted: {}", err),
+    }
+}
+
+fn play_single_round(args: &[String]) {
     let throw = RPSThrow::Paper;
     let other_throw = get_random_throw();
     let result = get_rps_state(&throw, &other_throw);
@@ -50,4 +513,85 @@ fn main() {
         "{:?} vs {:?} => {:?}({:?})",
         throw, other_throw, result, value
     );
+
+    let game = record::GameRecord {
+        label: "single".to_string(),
+        players: ("paper".to_string(), "random".to_string()),
+        seed: None,
+        scheme: scoring::ScoringScheme::Football,
+        rounds: vec![(throw, other_throw)],
+    };
+    write_log(&mut open_log(args), &game);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(|arg| arg.as_str()) {
+        None => play_single_round(&args),
+        Some(option) if option.starts_with("--") => play_single_round(&args),
+        Some("tournament") => run_tournament(&args[1..]),
+        Some("benchmark") => run_benchmark(&args[1..]),
+        Some("montecarlo") => run_montecarlo(&args[1..]),
+        Some("fairness") => run_fairness(&args[1..]),
+        Some("elimination") => run_elimination(&args[1..]),
+        Some("teams") => run_teams(&args[1..]),
+        Some("play") => run_interactive(&args[1..]),
+        Some("host") => run_network(&args[1..], true),
+        Some("join") => run_network(&args[1..], false),
+        Some("replay") => run_replay(&args[1..]),
+        Some("convert") => run_convert(&args[1..]),
+        Some(command) => {
+            eprintln!("Unknown command {:?}", command);
+            eprintln!("usage: av2-clone [--record FILE]");
+            eprintln!("       av2-clone tournament [--rounds N] [--seed S] [--scoring SCHEME] [--record FILE] [strategy...]");
+            eprintln!("       av2-clone benchmark [--rounds N] [--seed S]");
+            eprintln!("       av2-clone montecarlo [--samples N] [--rounds R] [--seed S] [--threads T] [--scoring SCHEME] STRATEGY STRATEGY");
+            eprintln!("       av2-clone fairness [--draws N] [--alpha A]");
+            eprintln!("       av2-clone elimination [--players N] [--games G] [--seed S]");
+            eprintln!("       av2-clone teams [--rounds R] [--seed S] STRATEGY,... STRATEGY,...");
+            eprintln!("       av2-clone play [--best-of N] [--opponent STRATEGY] [--seed S] [--scoring SCHEME] [--record FILE]");
+            eprintln!(
+                "       av2-clone host [--port P] [--best-of N] [--scoring SCHEME] [--record FILE]"
+            );
+            eprintln!("       av2-clone join [--port P] [--scoring SCHEME] [--record FILE]");
+            eprintln!("       av2-clone replay FILE");
+            eprintln!("       av2-clone convert [--column throw|outcome] FILE");
+            eprintln!("strategies: {}", strategy::STRATEGY_NAMES.join(", "));
+            eprintln!("scoring schemes: puzzle, football (default), zero-sum");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_option, get_positional, get_rps_state, RPSState, RPSThrow};
+
+    #[test]
+    fn test_beaten_by() {
+        for throw in RPSThrow::ALL {
+            assert!(matches!(
+                get_rps_state(&throw.beaten_by(), &throw),
+                RPSState::Win
+            ));
+        }
+    }
+
+    #[test]
+    fn test_args() {
+        let args: Vec<String> = ["--rounds", "5", "rock", "cycle"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert_eq!(Some(5), get_option::<usize>(&args, "--rounds"));
+        assert_eq!(None, get_option::<u64>(&args, "--seed"));
+        assert_eq!(vec!["rock", "cycle"], get_positional(&args));
+    }
+
+    #[test]
+    fn test_parse_throw() {
+        assert_eq!(RPSThrow::Scissors, "s".parse().unwrap());
+        assert_eq!(RPSThrow::Paper, "paper".parse().unwrap());
+        assert!("lizard".parse::<RPSThrow>().is_err());
+    }
 }
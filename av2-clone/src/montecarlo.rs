@@ -0,0 +1,158 @@
+use std::thread;
+
+use crate::{
+    scoring::ScoringScheme,
+    strategy::{mix_seed, strategy_from_name},
+    tournament::play_match,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationConfig {
+    /// Number of independent matches to simulate.
+    pub samples: usize,
+    /// Rounds per match.
+    pub rounds: usize,
+    pub seed: u64,
+    pub threads: usize,
+    pub scheme: ScoringScheme,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub mean: f64,
+    pub std_dev: f64,
+    /// 95% confidence interval of the mean, using the normal approximation.
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl Estimate {
+    pub fn from_samples(values: &[f64]) -> Self {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std_dev = if values.len() > 1 {
+            (values
+                .iter()
+                .map(|value| (value - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1.0))
+                .sqrt()
+        } else {
+            0.0
+        };
+        let margin = 1.96 * std_dev / n.sqrt();
+        Estimate {
+            mean,
+            std_dev,
+            ci_low: mean - margin,
+            ci_high: mean + margin,
+        }
+    }
+}
+
+/// Plays match `index` and returns the average score per round of both players.
+fn sample(a: &str, b: &str, config: &SimulationConfig, index: usize) -> (f64, f64) {
+    let index = index as u64;
+    let mut strategy_a = strategy_from_name(a, mix_seed(config.seed, 2 * index))
+        .unwrap_or_else(|| panic!("Unknown strategy {:?}", a));
+    let mut strategy_b = strategy_from_name(b, mix_seed(config.seed, 2 * index + 1))
+        .unwrap_or_else(|| panic!("Unknown strategy {:?}", b));
+
+    let history = play_match(strategy_a.as_mut(), strategy_b.as_mut(), config.rounds);
+    let (score_a, score_b) =
+        history
+            .iter()
+            .fold((0, 0), |(score_a, score_b), (throw, other_throw)| {
+                (
+                    score_a + config.scheme.score(throw, other_throw) as i64,
+                    score_b + config.scheme.score(other_throw, throw) as i64,
+                )
+            });
+    let rounds = config.rounds as f64;
+    (score_a as f64 / rounds, score_b as f64 / rounds)
+}
+
+/// Estimates the expected score per round of strategies `a` and `b` against each other.
+///
+/// Every sample has its own seed derived from `config.seed`, and samples are merged in index
+/// order, so the result only depends on the seed and not on the number of threads.
+pub fn simulate(a: &str, b: &str, config: &SimulationConfig) -> (Estimate, Estimate) {
+    assert!(config.samples > 0 && config.rounds > 0);
+    let threads = config.threads.clamp(1, config.samples);
+    let chunk_size = config.samples.div_ceil(threads);
+
+    let results: Vec<(f64, f64)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..config.samples)
+            .step_by(chunk_size)
+            .map(|start| {
+                let end = (start + chunk_size).min(config.samples);
+                scope.spawn(move || {
+                    (start..end)
+                        .map(|index| sample(a, b, config, index))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Simulation thread panicked"))
+            .collect()
+    });
+
+    let (scores_a, scores_b): (Vec<f64>, Vec<f64>) = results.into_iter().unzip();
+    (
+        Estimate::from_samples(&scores_a),
+        Estimate::from_samples(&scores_b),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::montecarlo::{simulate, Estimate, SimulationConfig};
+    use crate::scoring::ScoringScheme;
+
+    fn config(threads: usize) -> SimulationConfig {
+        SimulationConfig {
+            samples: 50,
+            rounds: 20,
+            seed: 2022,
+            threads,
+            scheme: ScoringScheme::Football,
+        }
+    }
+
+    #[test]
+    fn test_estimate() {
+        let estimate = Estimate::from_samples(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(2.5, estimate.mean);
+        assert!((estimate.std_dev - 1.290994).abs() < 1e-6);
+        assert!((estimate.ci_high - estimate.mean - 1.96 * 1.290994 / 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_deterministic_strategies_have_no_spread() {
+        let (paper, rock) = simulate("paper", "rock", &config(1));
+        assert_eq!((3.0, 0.0), (paper.mean, paper.std_dev));
+        assert_eq!((0.0, 0.0), (rock.mean, rock.ci_high));
+    }
+
+    #[test]
+    fn test_thread_count_does_not_change_result() {
+        let single = simulate("random", "markov-1", &config(1));
+        assert_eq!(single, simulate("random", "markov-1", &config(3)));
+        assert_eq!(single, simulate("random", "markov-1", &config(64)));
+    }
+
+    #[test]
+    fn test_random_against_random() {
+        let mut config = config(4);
+        config.samples = 400;
+        config.scheme = ScoringScheme::ZeroSum;
+        let (a, b) = simulate("random", "random", &config);
+
+        // the two random players must not share a seed, or every round would be a draw
+        assert!(a.std_dev > 0.0);
+        assert!(a.ci_low < 0.0 && 0.0 < a.ci_high, "{:?}", a);
+        assert!((a.mean + b.mean).abs() < 1e-12);
+    }
+}
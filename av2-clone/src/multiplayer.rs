@@ -0,0 +1,253 @@
+use rand::Rng;
+
+use crate::{
+    get_random_throw_from, get_rps_state,
+    strategy::{Round, Strategy},
+    RPSState, RPSThrow,
+};
+
+/// Resolves a round where every player throws at once.
+///
+/// If exactly two distinct throws appear, the players holding the winning one win; otherwise
+/// (everyone threw the same, or all three throws appear) the round is a draw and `None` is returned.
+pub fn resolve_round(throws: &[RPSThrow]) -> Option<Vec<usize>> {
+    let distinct: Vec<RPSThrow> = RPSThrow::ALL
+        .iter()
+        .copied()
+        .filter(|throw| throws.contains(throw))
+        .collect();
+    let winning_throw = match distinct[..] {
+        [a, b] => match get_rps_state(&a, &b) {
+            RPSState::Win => a,
+            _ => b,
+        },
+        _ => return None,
+    };
+    Some(
+        throws
+            .iter()
+            .enumerate()
+            .filter(|(_, throw)| **throw == winning_throw)
+            .map(|(index, _)| index)
+            .collect(),
+    )
+}
+
+/// The most players `elimination` simulates. A round only makes progress when exactly two
+/// distinct throws appear, with probability about 3 * 2^n / 3^n, so 20 players already need
+/// over a thousand rounds per game on average and every extra player adds half again.
+pub const MAX_PLAYERS: usize = 20;
+
+/// Plays elimination rounds with random throws until one player is left.
+///
+/// Returns the index of the winner and the number of rounds it took.
+pub fn play_elimination<R: Rng>(players: usize, rng: &mut R) -> (usize, u32) {
+    assert!(players > 0, "Should have at least one player");
+    let mut remaining: Vec<usize> = (0..players).collect();
+    let mut rounds = 0;
+    while remaining.len() > 1 {
+        rounds += 1;
+        let throws: Vec<RPSThrow> = remaining
+            .iter()
+            .map(|_| get_random_throw_from(rng))
+            .collect();
+        if let Some(winners) = resolve_round(&throws) {
+            remaining = winners.iter().map(|&index| remaining[index]).collect();
+        }
+    }
+    (remaining[0], rounds)
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Exact expected number of rounds until one of `players` random players is left.
+///
+/// With n players, each of the three winning pairs of throws leaves exactly k players
+/// (1 <= k < n) with probability C(n, k) / 3^n; every other outcome is a draw.
+pub fn expected_rounds(players: usize) -> f64 {
+    let mut expected = vec![0.0; players.max(1) + 1];
+    for n in 2..=players {
+        let outcomes = 3f64.powi(n as i32);
+        let mut progress = 0.0;
+        let mut sum = 1.0;
+        for (k, expected_k) in expected.iter().enumerate().take(n).skip(1) {
+            let p = 3.0 * binomial(n, k) / outcomes;
+            progress += p;
+            sum += p * expected_k;
+        }
+        expected[n] = sum / progress;
+    }
+    expected[players.max(1)]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EliminationStats {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub max: u32,
+}
+
+pub fn simulate_elimination<R: Rng>(players: usize, games: usize, rng: &mut R) -> EliminationStats {
+    assert!(games > 0, "Should simulate at least one game");
+    let rounds: Vec<u32> = (0..games)
+        .map(|_| play_elimination(players, rng).1)
+        .collect();
+    let n = games as f64;
+    let mean = rounds.iter().map(|&r| r as f64).sum::<f64>() / n;
+    let variance = rounds
+        .iter()
+        .map(|&r| (r as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    EliminationStats {
+        mean,
+        std_dev: variance.sqrt(),
+        max: rounds.iter().copied().max().unwrap_or(0),
+    }
+}
+
+/// Compares every member of one team with every member of the other; the team with more
+/// individual wins takes the round.
+pub fn resolve_team_round(team: &[RPSThrow], other_team: &[RPSThrow]) -> RPSState {
+    let balance: i32 = team
+        .iter()
+        .flat_map(|throw| other_team.iter().map(move |other| (throw, other)))
+        .map(|(throw, other)| match get_rps_state(throw, other) {
+            RPSState::Win => 1,
+            RPSState::Draw => 0,
+            RPSState::Loss => -1,
+        })
+        .sum();
+    match balance {
+        b if b > 0 => RPSState::Win,
+        0 => RPSState::Draw,
+        _ => RPSState::Loss,
+    }
+}
+
+/// Plays `rounds` team rounds. Member `i` of each team sees its history against member
+/// `i` of the other team (wrapping around when the teams differ in size).
+/// Returns (wins, draws, losses) for `team`.
+pub fn play_team_match(
+    team: &mut [Box<dyn Strategy>],
+    other_team: &mut [Box<dyn Strategy>],
+    rounds: usize,
+) -> (u32, u32, u32) {
+    team.iter_mut().for_each(|member| member.reset());
+    other_team.iter_mut().for_each(|member| member.reset());
+    let mut histories: Vec<Vec<Round>> = vec![vec![]; team.len()];
+    let mut other_histories: Vec<Vec<Round>> = vec![vec![]; other_team.len()];
+    let (mut wins, mut draws, mut losses) = (0, 0, 0);
+
+    for _ in 0..rounds {
+        let throws: Vec<RPSThrow> = team
+            .iter_mut()
+            .zip(&histories)
+            .map(|(member, history)| member.next_throw(history))
+            .collect();
+        let other_throws: Vec<RPSThrow> = other_team
+            .iter_mut()
+            .zip(&other_histories)
+            .map(|(member, history)| member.next_throw(history))
+            .collect();
+
+        for (i, history) in histories.iter_mut().enumerate() {
+            history.push((throws[i], other_throws[i % other_throws.len()]));
+        }
+        for (i, history) in other_histories.iter_mut().enumerate() {
+            history.push((other_throws[i], throws[i % throws.len()]));
+        }
+        match resolve_team_round(&throws, &other_throws) {
+            RPSState::Win => wins += 1,
+            RPSState::Draw => draws += 1,
+            RPSState::Loss => losses += 1,
+        }
+    }
+    (wins, draws, losses)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::multiplayer::{
+        expected_rounds, play_elimination, play_team_match, resolve_round, resolve_team_round,
+        simulate_elimination,
+    };
+    use crate::strategy::{Constant, Strategy};
+    use crate::{RPSState, RPSThrow};
+
+    use RPSThrow::{Paper, Rock, Scissors};
+
+    #[test]
+    fn test_resolve_round() {
+        assert_eq!(Some(vec![1, 3]), resolve_round(&[Rock, Paper, Rock, Paper]));
+        assert_eq!(Some(vec![0]), resolve_round(&[Rock, Scissors, Scissors]));
+        assert_eq!(None, resolve_round(&[Rock, Paper, Scissors, Rock]));
+        assert_eq!(None, resolve_round(&[Paper, Paper, Paper]));
+    }
+
+    #[test]
+    fn test_two_players_match_get_rps_state() {
+        for throw in RPSThrow::ALL {
+            for other_throw in RPSThrow::ALL {
+                let expected = match crate::get_rps_state(&throw, &other_throw) {
+                    RPSState::Win => Some(vec![0]),
+                    RPSState::Loss => Some(vec![1]),
+                    RPSState::Draw => None,
+                };
+                assert_eq!(expected, resolve_round(&[throw, other_throw]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_expected_rounds() {
+        assert_eq!(0.0, expected_rounds(1));
+        // two players finish whenever they don't draw
+        assert!((expected_rounds(2) - 1.5).abs() < 1e-12);
+        // three players: 18 of the 27 outcomes make progress, half of them leaving two players
+        assert!((expected_rounds(3) - 2.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_simulation_agrees_with_expectation() {
+        let mut rng = StdRng::seed_from_u64(2022);
+        let stats = simulate_elimination(4, 20_000, &mut rng);
+        assert!((stats.mean - expected_rounds(4)).abs() < 0.1, "{:?}", stats);
+    }
+
+    #[test]
+    fn test_elimination_leaves_one_winner() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (winner, rounds) = play_elimination(6, &mut rng);
+        assert!(winner < 6);
+        assert!(rounds >= 1);
+    }
+
+    #[test]
+    fn test_team_round() {
+        assert!(matches!(
+            resolve_team_round(&[Rock, Rock], &[Scissors, Paper]),
+            RPSState::Draw
+        ));
+        assert!(matches!(
+            resolve_team_round(&[Rock, Paper], &[Scissors, Scissors]),
+            RPSState::Draw
+        ));
+        assert!(matches!(
+            resolve_team_round(&[Rock, Rock], &[Scissors, Rock]),
+            RPSState::Win
+        ));
+    }
+
+    #[test]
+    fn test_team_match() {
+        let mut team: Vec<Box<dyn Strategy>> =
+            vec![Box::new(Constant(Paper)), Box::new(Constant(Paper))];
+        let mut other_team: Vec<Box<dyn Strategy>> = vec![Box::new(Constant(Rock))];
+        assert_eq!((5, 0, 0), play_team_match(&mut team, &mut other_team, 5));
+    }
+}
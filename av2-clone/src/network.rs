@@ -0,0 +1,266 @@
+//! Two-player games over a TCP connection.
+//!
+//! Every round uses a commit-reveal exchange so neither player can wait for the other's throw:
+//!
+//! ```text
+//! host -> join   BESTOF <n>              (once, when the connection opens)
+//! both           COMMIT <sha256(nonce:throw) as hex>
+//! both           REVEAL <throw> <nonce>
+//! either         QUIT
+//! ```
+
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    get_rps_state,
+    interactive::{read_command, Command},
+    strategy::Round,
+    RPSState, RPSThrow,
+};
+
+#[derive(Debug)]
+pub enum ProtocolError {
+    Io(io::Error),
+    Unexpected(String),
+    CommitMismatch,
+    Disconnected,
+}
+
+impl From<io::Error> for ProtocolError {
+    fn from(err: io::Error) -> Self {
+        ProtocolError::Io(err)
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::Io(err) => write!(f, "connection error: {}", err),
+            ProtocolError::Unexpected(line) => write!(f, "unexpected message {:?}", line),
+            ProtocolError::CommitMismatch => {
+                write!(f, "the revealed throw does not match the commitment")
+            }
+            ProtocolError::Disconnected => write!(f, "the other player disconnected"),
+        }
+    }
+}
+
+/// What the other side did in a round.
+#[derive(Debug, PartialEq)]
+pub enum PeerMove {
+    Throw(RPSThrow),
+    Quit,
+}
+
+pub fn commitment(throw: RPSThrow, nonce: &str) -> String {
+    let digest = Sha256::digest(format!("{}:{}", nonce, throw.name()).as_bytes());
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn read_message<R: BufRead>(reader: &mut R) -> Result<String, ProtocolError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(ProtocolError::Disconnected);
+    }
+    Ok(line.trim_end().to_string())
+}
+
+fn send_message<W: Write>(writer: &mut W, message: &str) -> Result<(), ProtocolError> {
+    writeln!(writer, "{}", message)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn send_best_of<W: Write>(writer: &mut W, best_of: u32) -> Result<(), ProtocolError> {
+    send_message(writer, &format!("BESTOF {}", best_of))
+}
+
+pub fn read_best_of<R: BufRead>(reader: &mut R) -> Result<u32, ProtocolError> {
+    let line = read_message(reader)?;
+    line.strip_prefix("BESTOF ")
+        .and_then(|best_of| best_of.parse().ok())
+        .ok_or(ProtocolError::Unexpected(line))
+}
+
+pub fn send_quit<W: Write>(writer: &mut W) -> Result<(), ProtocolError> {
+    send_message(writer, "QUIT")
+}
+
+/// Plays one round: commits to `throw`, waits for the peer's commitment, then both reveal.
+pub fn exchange_throws<R: BufRead, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    throw: RPSThrow,
+) -> Result<PeerMove, ProtocolError> {
+    let nonce = format!("{:032x}", rand::thread_rng().gen::<u128>());
+    send_message(writer, &format!("COMMIT {}", commitment(throw, &nonce)))?;
+
+    let line = read_message(reader)?;
+    let peer_commitment = match line.split_once(' ') {
+        Some(("COMMIT", hash)) => hash.to_string(),
+        _ if line == "QUIT" => return Ok(PeerMove::Quit),
+        _ => return Err(ProtocolError::Unexpected(line)),
+    };
+
+    send_message(writer, &format!("REVEAL {} {}", throw.name(), nonce))?;
+
+    let line = read_message(reader)?;
+    let parts: Vec<&str> = line.split(' ').collect();
+    let peer_throw = match parts[..] {
+        ["REVEAL", peer_throw, peer_nonce] => {
+            let peer_throw: RPSThrow = peer_throw
+                .parse()
+                .map_err(|_| ProtocolError::Unexpected(line.clone()))?;
+            if commitment(peer_throw, peer_nonce) != peer_commitment {
+                return Err(ProtocolError::CommitMismatch);
+            }
+            peer_throw
+        }
+        ["QUIT"] => return Ok(PeerMove::Quit),
+        _ => return Err(ProtocolError::Unexpected(line)),
+    };
+    Ok(PeerMove::Throw(peer_throw))
+}
+
+/// Plays a best-of-`best_of` game, reading the local player's throws from `input`.
+///
+/// Returns the rounds played from the local player's point of view.
+pub fn play_network<R, W, I, O>(
+    reader: &mut R,
+    writer: &mut W,
+    input: &mut I,
+    output: &mut O,
+    best_of: u32,
+) -> Result<Vec<Round>, ProtocolError>
+where
+    R: BufRead,
+    W: Write,
+    I: BufRead,
+    O: Write,
+{
+    let needed_wins = best_of / 2 + 1;
+    let mut history: Vec<Round> = vec![];
+    let (mut wins, mut draws, mut losses) = (0, 0, 0);
+    writeln!(output, "Best of {}: first to {} wins", best_of, needed_wins)?;
+
+    while wins < needed_wins && losses < needed_wins {
+        let throw = match read_command(input, output)? {
+            Command::Throw(throw) => throw,
+            Command::Quit => {
+                send_quit(writer)?;
+                writeln!(output, "Game abandoned")?;
+                break;
+            }
+        };
+        writeln!(output, "Waiting for the other player...")?;
+        let other_throw = match exchange_throws(reader, writer, throw)? {
+            PeerMove::Throw(other_throw) => other_throw,
+            PeerMove::Quit => {
+                writeln!(output, "The other player left the game")?;
+                break;
+            }
+        };
+        history.push((throw, other_throw));
+
+        let state = get_rps_state(&throw, &other_throw);
+        match state {
+            RPSState::Win => wins += 1,
+            RPSState::Draw => draws += 1,
+            RPSState::Loss => losses += 1,
+        }
+        writeln!(
+            output,
+            "Round {}: {:?} vs {:?} => {:?}",
+            wins + draws + losses,
+            throw,
+            other_throw,
+            state
+        )?;
+    }
+
+    writeln!(
+        output,
+        "Final score: {} wins, {} draws, {} losses",
+        wins, draws, losses
+    )?;
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    use crate::network::{commitment, exchange_throws, play_network, PeerMove, ProtocolError};
+    use crate::RPSThrow;
+
+    #[test]
+    fn test_commitment_depends_on_throw_and_nonce() {
+        let rock = commitment(RPSThrow::Rock, "abc");
+        assert_eq!(64, rock.len());
+        assert_eq!(rock, commitment(RPSThrow::Rock, "abc"));
+        assert_ne!(rock, commitment(RPSThrow::Paper, "abc"));
+        assert_ne!(rock, commitment(RPSThrow::Rock, "abd"));
+    }
+
+    #[test]
+    fn test_exchange_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let join = thread::spawn(move || {
+            let stream = TcpStream::connect(address).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            exchange_throws(&mut reader, &mut writer, RPSThrow::Scissors).unwrap()
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let host_saw = exchange_throws(&mut reader, &mut writer, RPSThrow::Rock).unwrap();
+
+        assert_eq!(PeerMove::Throw(RPSThrow::Scissors), host_saw);
+        assert_eq!(PeerMove::Throw(RPSThrow::Rock), join.join().unwrap());
+    }
+
+    #[test]
+    fn test_reveal_must_match_commitment() {
+        let peer = format!(
+            "COMMIT {}\nREVEAL paper n1\n",
+            commitment(RPSThrow::Rock, "n1")
+        );
+        let result = exchange_throws(&mut Cursor::new(peer), &mut vec![], RPSThrow::Rock);
+        assert!(matches!(result, Err(ProtocolError::CommitMismatch)));
+    }
+
+    #[test]
+    fn test_peer_quit() {
+        let result = exchange_throws(&mut Cursor::new("QUIT\n"), &mut vec![], RPSThrow::Rock);
+        assert_eq!(PeerMove::Quit, result.unwrap());
+    }
+
+    #[test]
+    fn test_play_network_returns_rounds() {
+        let peer = format!(
+            "COMMIT {}\nREVEAL paper n1\n",
+            commitment(RPSThrow::Paper, "n1")
+        );
+        let history = play_network(
+            &mut Cursor::new(peer),
+            &mut vec![],
+            &mut Cursor::new("rock\n"),
+            &mut vec![],
+            1,
+        )
+        .unwrap();
+        assert_eq!(vec![(RPSThrow::Rock, RPSThrow::Paper)], history);
+    }
+}
@@ -0,0 +1,311 @@
+//! Line-based game logs.
+//!
+//! ```text
+//! GAME <label> <player> <opponent> seed=<seed|none> scoring=<scheme>
+//! ROUND <n> <throw> <other throw> <state> <points> <total points>
+//! END
+//! ```
+//!
+//! Everything is written from the first player's point of view. The seed is the base seed given
+//! with `--seed`; every player derives its own stream from it with `strategy::mix_seed`.
+
+use std::io::{self, Write};
+
+use crate::{get_rps_state, scoring::ScoringScheme, strategy::Round, RPSState, RPSThrow};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    pub label: String,
+    pub players: (String, String),
+    /// The base seed of the command, not the seed of either player.
+    pub seed: Option<u64>,
+    pub scheme: ScoringScheme,
+    pub rounds: Vec<Round>,
+}
+
+/// A problem found while replaying a log, with the 1-based line it was found on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inconsistency {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Replaces whitespace so names always stay a single field.
+fn field(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+impl GameRecord {
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let seed = match self.seed {
+            Some(seed) => seed.to_string(),
+            None => "none".to_string(),
+        };
+        writeln!(
+            writer,
+            "GAME {} {} {} seed={} scoring={}",
+            field(&self.label),
+            field(&self.players.0),
+            field(&self.players.1),
+            seed,
+            self.scheme.name()
+        )?;
+
+        let mut total = 0;
+        for (index, (throw, other_throw)) in self.rounds.iter().enumerate() {
+            let points = self.scheme.score(throw, other_throw);
+            total += points;
+            writeln!(
+                writer,
+                "ROUND {} {} {} {:?} {} {}",
+                index + 1,
+                throw.name(),
+                other_throw.name(),
+                get_rps_state(throw, other_throw),
+                points,
+                total
+            )?;
+        }
+        writeln!(writer, "END")
+    }
+}
+
+fn parse_header(line: &str) -> Result<GameRecord, String> {
+    let fields: Vec<&str> = line.split(' ').collect();
+    let (label, player, opponent, seed, scheme) = match fields[..] {
+        ["GAME", label, player, opponent, seed, scheme] => (label, player, opponent, seed, scheme),
+        _ => return Err(format!("malformed game header {:?}", line)),
+    };
+    let seed = match seed.strip_prefix("seed=") {
+        Some("none") => None,
+        Some(seed) => Some(
+            seed.parse()
+                .map_err(|_| format!("invalid seed {:?}", seed))?,
+        ),
+        None => return Err(format!("missing seed in {:?}", line)),
+    };
+    let scheme = scheme
+        .strip_prefix("scoring=")
+        .ok_or(format!("missing scoring in {:?}", line))?
+        .parse::<ScoringScheme>()
+        .map_err(|err| err.to_string())?;
+
+    Ok(GameRecord {
+        label: label.to_string(),
+        players: (player.to_string(), opponent.to_string()),
+        seed,
+        scheme,
+        rounds: vec![],
+    })
+}
+
+/// Parses a `ROUND` line, checks it against the rules and appends the throws to `game`.
+fn replay_round(line: &str, game: &mut GameRecord, total: &mut i64) -> Result<(), String> {
+    let fields: Vec<&str> = line.split(' ').collect();
+    let (number, throw, other_throw, state, points, recorded_total) = match fields[..] {
+        ["ROUND", number, throw, other_throw, state, points, total] => {
+            (number, throw, other_throw, state, points, total)
+        }
+        _ => return Err(format!("malformed round {:?}", line)),
+    };
+    let throw: RPSThrow = throw.parse().map_err(|err| format!("{}", err))?;
+    let other_throw: RPSThrow = other_throw.parse().map_err(|err| format!("{}", err))?;
+    game.rounds.push((throw, other_throw));
+
+    let mut problems = vec![];
+    if number != game.rounds.len().to_string() {
+        problems.push(format!(
+            "round numbered {} but it is round {}",
+            number,
+            game.rounds.len()
+        ));
+    }
+    let actual_state = get_rps_state(&throw, &other_throw);
+    if state != format!("{:?}", actual_state) {
+        problems.push(format!(
+            "recorded {} but {:?} vs {:?} is a {:?}",
+            state, throw, other_throw, actual_state
+        ));
+    }
+    let actual_points = game.scheme.score(&throw, &other_throw) as i64;
+    *total += actual_points;
+    if points.parse::<i64>().ok() != Some(actual_points) {
+        problems.push(format!(
+            "recorded {} points but {:?} scoring gives {}",
+            points, game.scheme, actual_points
+        ));
+    }
+    if recorded_total.parse::<i64>().ok() != Some(*total) {
+        problems.push(format!(
+            "recorded a total of {} but the rounds add up to {}",
+            recorded_total, total
+        ));
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+/// Re-reads a log, re-checking every round with `get_rps_state`.
+///
+/// Returns every game that could be read together with every inconsistency found.
+pub fn replay(content: &str) -> (Vec<GameRecord>, Vec<Inconsistency>) {
+    let mut games = vec![];
+    let mut problems = vec![];
+    let mut current: Option<(GameRecord, i64)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let mut report = |message: String| {
+            problems.push(Inconsistency {
+                line: line_number,
+                message,
+            })
+        };
+        let line = line.trim_end();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        } else if line.starts_with("GAME ") {
+            if let Some((game, _)) = current.take() {
+                report(format!("game {:?} has no END", game.label));
+                games.push(game);
+            }
+            match parse_header(line) {
+                Ok(game) => current = Some((game, 0)),
+                Err(message) => report(message),
+            }
+        } else if line.starts_with("ROUND ") {
+            match current.as_mut() {
+                Some((game, total)) => {
+                    if let Err(message) = replay_round(line, game, total) {
+                        report(message);
+                    }
+                }
+                None => report("round outside of a game".to_string()),
+            }
+        } else if line == "END" {
+            match current.take() {
+                Some((game, _)) => games.push(game),
+                None => report("END outside of a game".to_string()),
+            }
+        } else {
+            report(format!("unknown line {:?}", line));
+        }
+    }
+    if let Some((game, _)) = current {
+        problems.push(Inconsistency {
+            line: content.lines().count(),
+            message: format!("game {:?} has no END", game.label),
+        });
+        games.push(game);
+    }
+    (games, problems)
+}
+
+fn opponent_letter(throw: &RPSThrow) -> char {
+    match throw {
+        RPSThrow::Rock => 'A',
+        RPSThrow::Paper => 'B',
+        RPSThrow::Scissors => 'C',
+    }
+}
+
+/// Converts the rounds to strategy-guide lines ("A Y"), opponent first.
+///
+/// The second column is the first player's throw, or with `outcome` the desired result
+/// (X lose, Y draw, Z win) as in the second part of the puzzle.
+pub fn to_strategy_guide(games: &[GameRecord], outcome: bool) -> String {
+    games
+        .iter()
+        .flat_map(|game| game.rounds.iter())
+        .map(|(throw, other_throw)| {
+            let mine = if outcome {
+                match get_rps_state(throw, other_throw) {
+                    RPSState::Loss => 'X',
+                    RPSState::Draw => 'Y',
+                    RPSState::Win => 'Z',
+                }
+            } else {
+                match throw {
+                    RPSThrow::Rock => 'X',
+                    RPSThrow::Paper => 'Y',
+                    RPSThrow::Scissors => 'Z',
+                }
+            };
+            format!("{} {}\n", opponent_letter(other_throw), mine)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::record::{replay, to_strategy_guide, GameRecord};
+    use crate::scoring::ScoringScheme;
+    use crate::RPSThrow;
+
+    fn example() -> GameRecord {
+        GameRecord {
+            label: "example".to_string(),
+            players: ("me".to_string(), "other player".to_string()),
+            seed: Some(42),
+            scheme: ScoringScheme::Puzzle,
+            rounds: vec![
+                (RPSThrow::Paper, RPSThrow::Rock),
+                (RPSThrow::Rock, RPSThrow::Paper),
+                (RPSThrow::Scissors, RPSThrow::Scissors),
+            ],
+        }
+    }
+
+    fn write(game: &GameRecord) -> String {
+        let mut log = vec![];
+        game.write_to(&mut log).unwrap();
+        String::from_utf8(log).unwrap()
+    }
+
+    #[test]
+    fn test_write_and_replay_round_trip() {
+        let log = write(&example());
+        assert!(log.starts_with("GAME example me other_player seed=42 scoring=puzzle\n"));
+        assert!(log.contains("ROUND 3 scissors scissors Draw 6 15\n"));
+
+        let (games, problems) = replay(&log);
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(example().rounds, games[0].rounds);
+        assert_eq!(Some(42), games[0].seed);
+    }
+
+    #[test]
+    fn test_replay_reports_tampered_rounds() {
+        let log = write(&example())
+            .replace("ROUND 2 rock paper Loss 1 9", "ROUND 2 rock paper Win 7 15")
+            .replace("ROUND 3 scissors", "ROUND 3 lizard");
+        let (_, problems) = replay(&log);
+
+        assert_eq!(3, problems[0].line);
+        assert!(problems[0].message.contains("recorded Win"));
+        assert!(problems[0].message.contains("recorded 7 points"));
+        assert!(problems[0].message.contains("recorded a total of 15"));
+        assert_eq!(4, problems[1].line);
+        assert_eq!(2, problems.len());
+    }
+
+    #[test]
+    fn test_replay_reports_missing_end() {
+        let log = write(&example()).replace("END\n", "");
+        let (games, problems) = replay(&log);
+        assert_eq!(1, games.len());
+        assert!(problems[0].message.contains("has no END"));
+    }
+
+    #[test]
+    fn test_to_strategy_guide() {
+        let games = [example()];
+        assert_eq!("A Y\nB X\nC Z\n", to_strategy_guide(&games, false));
+        assert_eq!("A Z\nB X\nC Y\n", to_strategy_guide(&games, true));
+    }
+}
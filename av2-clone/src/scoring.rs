@@ -0,0 +1,119 @@
+use crate::{get_rps_state, get_rps_state_value, RPSState, RPSThrow};
+
+/// How a single round is turned into points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScoringScheme {
+    /// The strategy-guide rules: shape points (1/2/3) plus 6 for a win, 3 for a draw.
+    Puzzle,
+    /// 3 points for a win, 1 for a draw, 0 for a loss.
+    #[default]
+    Football,
+    /// +1 for a win, 0 for a draw, -1 for a loss.
+    ZeroSum,
+}
+
+#[derive(Debug)]
+pub enum ParseScoringError {
+    Unknown(String),
+}
+
+impl std::fmt::Display for ParseScoringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseScoringError::Unknown(s) => write!(f, "unknown scoring scheme {:?}", s),
+        }
+    }
+}
+
+impl ScoringScheme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoringScheme::Puzzle => "puzzle",
+            ScoringScheme::Football => "football",
+            ScoringScheme::ZeroSum => "zero-sum",
+        }
+    }
+
+    pub fn score(&self, throw: &RPSThrow, other_throw: &RPSThrow) -> i32 {
+        let state = get_rps_state(throw, other_throw);
+        match self {
+            ScoringScheme::Puzzle => {
+                let outcome = match state {
+                    RPSState::Win => 6,
+                    RPSState::Draw => 3,
+                    RPSState::Loss => 0,
+                };
+                throw.shape_value() + outcome
+            }
+            ScoringScheme::Football => get_rps_state_value(&state) as i32,
+            ScoringScheme::ZeroSum => match state {
+                RPSState::Win => 1,
+                RPSState::Draw => 0,
+                RPSState::Loss => -1,
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for ScoringScheme {
+    type Err = ParseScoringError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "puzzle" => Ok(ScoringScheme::Puzzle),
+            "football" => Ok(ScoringScheme::Football),
+            "zero-sum" => Ok(ScoringScheme::ZeroSum),
+            _ => Err(ParseScoringError::Unknown(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoring::ScoringScheme;
+    use crate::RPSThrow;
+
+    #[test]
+    fn test_puzzle_matches_strategy_guide_example() {
+        // "A Y", "B X", "C Z" from the day 2 example, written as (my throw, their throw)
+        let games = [
+            (RPSThrow::Paper, RPSThrow::Rock),
+            (RPSThrow::Rock, RPSThrow::Paper),
+            (RPSThrow::Scissors, RPSThrow::Scissors),
+        ];
+        let scores: Vec<i32> = games
+            .iter()
+            .map(|(throw, other_throw)| ScoringScheme::Puzzle.score(throw, other_throw))
+            .collect();
+        assert_eq!(vec![8, 1, 6], scores);
+    }
+
+    #[test]
+    fn test_football_and_zero_sum() {
+        let rounds = [
+            (RPSThrow::Rock, RPSThrow::Scissors),
+            (RPSThrow::Rock, RPSThrow::Rock),
+            (RPSThrow::Rock, RPSThrow::Paper),
+        ];
+        let score = |scheme: ScoringScheme| -> Vec<i32> {
+            rounds
+                .iter()
+                .map(|(throw, other_throw)| scheme.score(throw, other_throw))
+                .collect()
+        };
+        assert_eq!(vec![3, 1, 0], score(ScoringScheme::Football));
+        assert_eq!(vec![1, 0, -1], score(ScoringScheme::ZeroSum));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ScoringScheme::ZeroSum, "zero-sum".parse().unwrap());
+        for scheme in [
+            ScoringScheme::Puzzle,
+            ScoringScheme::Football,
+            ScoringScheme::ZeroSum,
+        ] {
+            assert_eq!(scheme, scheme.name().parse().unwrap());
+        }
+        assert!("chess".parse::<ScoringScheme>().is_err());
+    }
+}
@@ -0,0 +1,265 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::StdRng, SeedableRng};
+
+use crate::{adaptive, get_random_throw_from, RPSThrow};
+
+/// One played round from the point of view of the strategy: (own throw, opponent throw).
+pub type Round = (RPSThrow, RPSThrow);
+
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// Picks the next throw given every round played so far in the current match.
+    fn next_throw(&mut self, history: &[Round]) -> RPSThrow;
+
+    /// Called before a new match starts so stateful strategies can start fresh.
+    fn reset(&mut self) {}
+}
+
+pub struct Constant(pub RPSThrow);
+
+impl Strategy for Constant {
+    fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+
+    fn next_throw(&mut self, _history: &[Round]) -> RPSThrow {
+        self.0
+    }
+}
+
+pub struct Random {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn next_throw(&mut self, _history: &[Round]) -> RPSThrow {
+        get_random_throw_from(&mut self.rng)
+    }
+
+    fn reset(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+    }
+}
+
+/// Random throws drawn with the given Rock/Paper/Scissors weights.
+pub struct Biased {
+    weights: [u32; 3],
+    distribution: WeightedIndex<u32>,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Biased {
+    pub fn new(weights: [u32; 3], seed: u64) -> Self {
+        Biased {
+            weights,
+            distribution: WeightedIndex::new(weights).expect("Should have a positive weight"),
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for Biased {
+    fn name(&self) -> String {
+        format!(
+            "biased-{}-{}-{}",
+            self.weights[0], self.weights[1], self.weights[2]
+        )
+    }
+
+    fn next_throw(&mut self, _history: &[Round]) -> RPSThrow {
+        RPSThrow::ALL[self.distribution.sample(&mut self.rng)]
+    }
+
+    fn reset(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+    }
+}
+
+/// Repeats a fixed sequence of throws.
+pub struct Pattern(Vec<RPSThrow>);
+
+impl Pattern {
+    pub fn new(throws: Vec<RPSThrow>) -> Self {
+        assert!(!throws.is_empty(), "Pattern should have at least one throw");
+        Pattern(throws)
+    }
+}
+
+impl Strategy for Pattern {
+    fn name(&self) -> String {
+        let letters: String = self
+            .0
+            .iter()
+            .map(|throw| match throw {
+                RPSThrow::Rock => 'r',
+                RPSThrow::Paper => 'p',
+                RPSThrow::Scissors => 's',
+            })
+            .collect();
+        format!("pattern-{}", letters)
+    }
+
+    fn next_throw(&mut self, history: &[Round]) -> RPSThrow {
+        self.0[history.len() % self.0.len()]
+    }
+}
+
+/// Plays Rock, Paper, Scissors, Rock, ... regardless of the opponent.
+pub struct Cycle;
+
+impl Strategy for Cycle {
+    fn name(&self) -> String {
+        "cycle".to_string()
+    }
+
+    fn next_throw(&mut self, history: &[Round]) -> RPSThrow {
+        RPSThrow::ALL[history.len() % RPSThrow::ALL.len()]
+    }
+}
+
+/// Repeats whatever the opponent threw last round, opening with Rock.
+pub struct CopyLast;
+
+impl Strategy for CopyLast {
+    fn name(&self) -> String {
+        "copy-last".to_string()
+    }
+
+    fn next_throw(&mut self, history: &[Round]) -> RPSThrow {
+        match history.last() {
+            Some((_, other_throw)) => *other_throw,
+            None => RPSThrow::Rock,
+        }
+    }
+}
+
+/// Plays the throw that would have beaten the opponent's last throw, opening with Rock.
+pub struct BeatLast;
+
+impl Strategy for BeatLast {
+    fn name(&self) -> String {
+        "beat-last".to_string()
+    }
+
+    fn next_throw(&mut self, history: &[Round]) -> RPSThrow {
+        match history.last() {
+            Some((_, other_throw)) => other_throw.beaten_by(),
+            None => RPSThrow::Rock,
+        }
+    }
+}
+
+pub const STRATEGY_NAMES: [&str; 10] = [
+    "rock",
+    "paper",
+    "scissors",
+    "random",
+    "cycle",
+    "copy-last",
+    "beat-last",
+    "frequency",
+    "markov-1",
+    "markov-2",
+];
+
+/// SplitMix64, used to derive an independent seed for every player from one base seed, so no
+/// two random players read the same stream.
+pub fn mix_seed(seed: u64, stream: u64) -> u64 {
+    let mut z = seed.wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9e3779b97f4a7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+pub fn strategy_from_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    let strategy: Box<dyn Strategy> = match name {
+        "rock" => Box::new(Constant(RPSThrow::Rock)),
+        "paper" => Box::new(Constant(RPSThrow::Paper)),
+        "scissors" => Box::new(Constant(RPSThrow::Scissors)),
+        "random" => Box::new(Random::new(seed)),
+        "cycle" => Box::new(Cycle),
+        "copy-last" => Box::new(CopyLast),
+        "beat-last" => Box::new(BeatLast),
+        "frequency" => Box::new(adaptive::FrequencyCounter::default()),
+        _ => match name
+            .strip_prefix("markov-")
+            .map(|order| order.parse::<usize>())
+        {
+            Some(Ok(order)) if order > 0 => Box::new(adaptive::Markov::new(order)),
+            _ => return None,
+        },
+    };
+    Some(strategy)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{strategy_from_name, BeatLast, CopyLast, Cycle, Random, Strategy};
+    use crate::RPSThrow;
+
+    #[test]
+    fn test_cycle() {
+        let throws: Vec<RPSThrow> = (0..4)
+            .map(|n| Cycle.next_throw(&vec![(RPSThrow::Rock, RPSThrow::Rock); n]))
+            .collect();
+        assert_eq!(
+            vec![
+                RPSThrow::Rock,
+                RPSThrow::Paper,
+                RPSThrow::Scissors,
+                RPSThrow::Rock
+            ],
+            throws
+        );
+    }
+
+    #[test]
+    fn test_reacts_to_last_throw() {
+        let history = [
+            (RPSThrow::Rock, RPSThrow::Paper),
+            (RPSThrow::Rock, RPSThrow::Scissors),
+        ];
+        assert_eq!(RPSThrow::Rock, CopyLast.next_throw(&[]));
+        assert_eq!(RPSThrow::Scissors, CopyLast.next_throw(&history));
+        assert_eq!(RPSThrow::Rock, BeatLast.next_throw(&history));
+    }
+
+    #[test]
+    fn test_random_reset_repeats_sequence() {
+        let mut random = Random::new(7);
+        let first: Vec<RPSThrow> = (0..20).map(|_| random.next_throw(&[])).collect();
+        random.reset();
+        let second: Vec<RPSThrow> = (0..20).map(|_| random.next_throw(&[])).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_strategy_from_name() {
+        assert_eq!(
+            "copy-last",
+            strategy_from_name("copy-last", 0).unwrap().name()
+        );
+        assert_eq!(
+            "markov-3",
+            strategy_from_name("markov-3", 0).unwrap().name()
+        );
+        assert!(strategy_from_name("markov-0", 0).is_none());
+        assert!(strategy_from_name("unknown", 0).is_none());
+    }
+}
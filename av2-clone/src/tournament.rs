@@ -0,0 +1,200 @@
+use std::cmp::Ordering;
+
+use crate::{get_rps_state, scoring::ScoringScheme, strategy::Round, strategy::Strategy, RPSState};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub points: i64,
+}
+
+/// Plays `rounds` rounds between two strategies and returns them from `a`'s point of view.
+pub fn play_match(a: &mut dyn Strategy, b: &mut dyn Strategy, rounds: usize) -> Vec<Round> {
+    a.reset();
+    b.reset();
+
+    let mut history_a: Vec<Round> = Vec::with_capacity(rounds);
+    let mut history_b: Vec<Round> = Vec::with_capacity(rounds);
+    for _ in 0..rounds {
+        let throw_a = a.next_throw(&history_a);
+        let throw_b = b.next_throw(&history_b);
+        history_a.push((throw_a, throw_b));
+        history_b.push((throw_b, throw_a));
+    }
+    history_a
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RoundSummary {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl RoundSummary {
+    pub fn total(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Share of the rounds that `count` represents, in percent.
+    pub fn percent(&self, count: u32) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => 100.0 * count as f64 / total as f64,
+        }
+    }
+}
+
+/// Counts the round outcomes of a match from the first player's point of view.
+pub fn round_summary(rounds: &[Round]) -> RoundSummary {
+    let mut summary = RoundSummary::default();
+    for (throw, other_throw) in rounds {
+        match get_rps_state(throw, other_throw) {
+            RPSState::Win => summary.wins += 1,
+            RPSState::Draw => summary.draws += 1,
+            RPSState::Loss => summary.losses += 1,
+        }
+    }
+    summary
+}
+
+fn match_points(rounds: &[Round], scheme: ScoringScheme) -> (i64, i64) {
+    rounds
+        .iter()
+        .map(|(throw, other_throw)| {
+            (
+                scheme.score(throw, other_throw) as i64,
+                scheme.score(other_throw, throw) as i64,
+            )
+        })
+        .fold((0, 0), |(sum_a, sum_b), (a, b)| (sum_a + a, sum_b + b))
+}
+
+fn record(standing: &mut Standing, state: RPSState, points: i64) {
+    match state {
+        RPSState::Win => standing.wins += 1,
+        RPSState::Draw => standing.draws += 1,
+        RPSState::Loss => standing.losses += 1,
+    }
+    standing.points += points;
+}
+
+/// Plays every strategy against every other one and returns the standings, best first.
+///
+/// `on_match` is called with both names and the rounds of every match once it is played.
+pub fn run_tournament(
+    strategies: &mut [Box<dyn Strategy>],
+    rounds: usize,
+    scheme: ScoringScheme,
+    on_match: &mut dyn FnMut(&str, &str, &[Round]),
+) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .map(|strategy| Standing {
+            name: strategy.name(),
+            ..Default::default()
+        })
+        .collect();
+
+    for i in 0..strategies.len() {
+        for j in i + 1..strategies.len() {
+            let (left, right) = strategies.split_at_mut(j);
+            let history = play_match(left[i].as_mut(), right[0].as_mut(), rounds);
+            on_match(&left[i].name(), &right[0].name(), &history);
+            let (points_i, points_j) = match_points(&history, scheme);
+
+            let (state_i, state_j) = match points_i.cmp(&points_j) {
+                Ordering::Greater => (RPSState::Win, RPSState::Loss),
+                Ordering::Less => (RPSState::Loss, RPSState::Win),
+                Ordering::Equal => (RPSState::Draw, RPSState::Draw),
+            };
+            record(&mut standings[i], state_i, points_i);
+            record(&mut standings[j], state_j, points_j);
+        }
+    }
+
+    standings.sort_by(|a, b| b.points.cmp(&a.points).then_with(|| a.name.cmp(&b.name)));
+    standings
+}
+
+pub fn print_standings(standings: &[Standing]) {
+    println!(
+        "{:>3}  {:<20} {:>4} {:>4} {:>4} {:>8}",
+        "#", "strategy", "W", "D", "L", "points"
+    );
+    for (place, standing) in standings.iter().enumerate() {
+        println!(
+            "{:>3}  {:<20} {:>4} {:>4} {:>4} {:>8}",
+            place + 1,
+            standing.name,
+            standing.wins,
+            standing.draws,
+            standing.losses,
+            standing.points
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoring::ScoringScheme;
+    use crate::strategy::{BeatLast, Constant, Cycle, Strategy};
+    use crate::tournament::{play_match, run_tournament};
+    use crate::RPSThrow;
+
+    #[test]
+    fn test_play_match_mirrors_throws() {
+        let history = play_match(&mut Constant(RPSThrow::Rock), &mut Cycle, 3);
+        assert_eq!(
+            vec![
+                (RPSThrow::Rock, RPSThrow::Rock),
+                (RPSThrow::Rock, RPSThrow::Paper),
+                (RPSThrow::Rock, RPSThrow::Scissors)
+            ],
+            history
+        );
+    }
+
+    #[test]
+    fn test_beat_last_wins_against_constant() {
+        let mut strategies: Vec<Box<dyn Strategy>> =
+            vec![Box::new(Constant(RPSThrow::Scissors)), Box::new(BeatLast)];
+        let standings = run_tournament(
+            &mut strategies,
+            10,
+            ScoringScheme::Football,
+            &mut |_, _, _| {},
+        );
+
+        assert_eq!("beat-last", standings[0].name);
+        assert_eq!(
+            (1, 0, 0),
+            (standings[0].wins, standings[0].draws, standings[0].losses)
+        );
+        // Rock opens against Scissors, so every round is a win
+        assert_eq!(10 * 3, standings[0].points);
+        assert_eq!(0, standings[1].points);
+    }
+
+    #[test]
+    fn test_puzzle_scoring_counts_shapes() {
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Constant(RPSThrow::Scissors)),
+            Box::new(Constant(RPSThrow::Paper)),
+        ];
+        let standings =
+            run_tournament(&mut strategies, 4, ScoringScheme::Puzzle, &mut |_, _, _| {});
+
+        assert_eq!(
+            ("scissors".to_string(), 4 * (3 + 6)),
+            (standings[0].name.clone(), standings[0].points)
+        );
+        assert_eq!(
+            ("paper".to_string(), 4 * 2),
+            (standings[1].name.clone(), standings[1].points)
+        );
+    }
+}
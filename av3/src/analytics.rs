@@ -0,0 +1,137 @@
+use std::{
+    cmp::Reverse,
+    io::{self, Write},
+};
+
+use crate::{
+    find_common_item, get_item, get_item_value, item_set::ItemSet, split_into_compartments,
+};
+
+/// How one item type shows up across the rucksack list.
+#[derive(Debug, PartialEq)]
+pub struct ItemStats {
+    pub item: u8,
+    /// Rucksacks carrying the item at least once.
+    pub rucksacks: u32,
+    /// Rucksacks where the item is the compartment duplicate.
+    pub duplicates: u32,
+    /// Groups where the item is the badge.
+    pub badges: u32,
+}
+
+/// Counts every item that appears at least once, most widespread first and by priority on ties.
+/// Ambiguous rucksacks and groups count their highest priority item.
+pub fn item_stats(rucksacks: &[&[u8]], group_size: usize) -> Vec<ItemStats> {
+    let mut stats: Vec<ItemStats> = (1..=52)
+        .map(|value| ItemStats {
+            item: get_item(value),
+            rucksacks: 0,
+            duplicates: 0,
+            badges: 0,
+        })
+        .collect();
+    let entry = |c: u8| get_item_value(c) as usize - 1;
+
+    for rucksack in rucksacks {
+        let (com_a, com_b) = split_into_compartments(rucksack);
+        let (items_a, items_b) = (ItemSet::from(com_a), ItemSet::from(com_b));
+        for item in items_a.union(&items_b).iter() {
+            stats[entry(item)].rucksacks += 1;
+        }
+        if let Some(duplicate) = items_a.intersection(&items_b).max() {
+            stats[entry(duplicate)].duplicates += 1;
+        }
+    }
+    for group in rucksacks.chunks_exact(group_size) {
+        if let Some(badge) = find_common_item(group) {
+            stats[entry(badge)].badges += 1;
+        }
+    }
+
+    stats.retain(|stat| stat.rucksacks > 0);
+    stats.sort_by_key(|stat| (Reverse(stat.rucksacks), get_item_value(stat.item)));
+    stats
+}
+
+pub fn print_table(stats: &[ItemStats]) {
+    println!("item priority rucksacks duplicates badges");
+    for stat in stats {
+        println!(
+            "{:>4} {:>8} {:>9} {:>10} {:>6}",
+            stat.item as char,
+            get_item_value(stat.item),
+            stat.rucksacks,
+            stat.duplicates,
+            stat.badges
+        );
+    }
+}
+
+pub fn write_csv(stats: &[ItemStats], output: &mut impl Write) -> io::Result<()> {
+    writeln!(output, "item,priority,rucksacks,duplicates,badges")?;
+    for stat in stats {
+        writeln!(
+            output,
+            "{},{},{},{},{}",
+            stat.item as char,
+            get_item_value(stat.item),
+            stat.rucksacks,
+            stat.duplicates,
+            stat.badges
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analytics::{item_stats, write_csv, ItemStats};
+
+    #[test]
+    fn test_item_stats() {
+        let rucksacks: [&[u8]; 4] = [b"abcb", b"adda", b"bAAb", b"xyzw"];
+        let stats = item_stats(&rucksacks, 2);
+        let stat = |item: u8| stats.iter().find(|stat| stat.item == item).unwrap();
+
+        // the duplicates are b, d and A; a is the badge of the first group
+        assert_eq!(
+            &ItemStats {
+                item: b'a',
+                rucksacks: 2,
+                duplicates: 0,
+                badges: 1
+            },
+            stat(b'a')
+        );
+        assert_eq!(
+            (2, 1, 0),
+            (
+                stat(b'b').rucksacks,
+                stat(b'b').duplicates,
+                stat(b'b').badges
+            )
+        );
+        assert_eq!(
+            (1, 1, 0),
+            (
+                stat(b'd').rucksacks,
+                stat(b'd').duplicates,
+                stat(b'd').badges
+            )
+        );
+        assert_eq!(1, stat(b'A').duplicates);
+        assert_eq!(9, stats.len());
+        assert_eq!([b'a', b'b'], [stats[0].item, stats[1].item]);
+    }
+
+    #[test]
+    fn test_csv() {
+        let stats = item_stats(&[b"aZaZ"], 1);
+        let mut csv = vec![];
+        write_csv(&stats, &mut csv).unwrap();
+        assert_eq!(
+            "item,priority,rucksacks,duplicates,badges\na,1,1,0,0\nZ,52,1,1,1\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::{collections::HashSet, time::Instant};
+
+use crate::{
+    checked_sums, find_common_item, find_duplicate, get_item_value, parallel::parallel_sums,
+    split_into_compartments,
+};
+
+/// The original `HashSet` implementation, kept as the baseline to compare against.
+fn find_duplicate_hashset(a: &[u8], b: &[u8]) -> Option<u8> {
+    let items_a: HashSet<u8> = a.iter().copied().collect();
+    let items_b: HashSet<u8> = b.iter().copied().collect();
+    let duplicate = items_a.intersection(&items_b).next().copied();
+    duplicate
+}
+
+fn find_duplicate_three_hashset(a: &[u8], b: &[u8], c: &[u8]) -> Option<u8> {
+    let items_a: HashSet<u8> = a.iter().copied().collect();
+    let items_b: HashSet<u8> = b.iter().copied().collect();
+    let items_c: HashSet<u8> = c.iter().copied().collect();
+    let items_a_b: HashSet<u8> = items_a.intersection(&items_b).copied().collect();
+    let badge = items_a_b.intersection(&items_c).next().copied();
+    badge
+}
+
+type DuplicateFinder = fn(&[u8], &[u8]) -> Option<u8>;
+type BadgeFinder = fn(&[u8], &[u8], &[u8]) -> Option<u8>;
+
+fn sums(lines: &[&[u8]], find_duplicate: DuplicateFinder, find_badge: BadgeFinder) -> (u32, u32) {
+    let rucksack_value_sum = lines
+        .iter()
+        .map(|rucksack| split_into_compartments(rucksack))
+        .map(|(com_a, com_b)| find_duplicate(com_a, com_b).unwrap())
+        .map(|c| get_item_value(c) as u32)
+        .sum();
+    let elf_badge_value_sum = lines
+        .chunks(3)
+        .map(|x| find_badge(x[0], x[1], x[2]).unwrap())
+        .map(|c| get_item_value(c) as u32)
+        .sum();
+    (rucksack_value_sum, elf_badge_value_sum)
+}
+
+/// Times both implementations on the same input and prints the speed-up, then the checked
+/// ItemSet sums of the command line on one thread and spread over `threads` threads.
+pub fn run_benchmark(lines: &[&[u8]], threads: usize) {
+    let start = Instant::now();
+    let hashset = sums(lines, find_duplicate_hashset, find_duplicate_three_hashset);
+    let hashset_time = start.elapsed();
+
+    let start = Instant::now();
+    let bitset = sums(lines, find_duplicate, |a, b, c| {
+        find_common_item(&[a, b, c])
+    });
+    let bitset_time = start.elapsed();
+
+    let start = Instant::now();
+    let checked = checked_sums(lines, 3, false).unwrap();
+    let checked_time = start.elapsed();
+
+    let start = Instant::now();
+    let parallel = parallel_sums(lines, 3, threads, false).unwrap();
+    let parallel_time = start.elapsed();
+
+    assert_eq!(hashset, bitset, "Both implementations should agree");
+    assert_eq!(bitset, checked, "The checked sums should agree");
+    assert_eq!(bitset, parallel, "The parallel sums should agree");
+    println!("{} rucksacks, sums {:?}", lines.len(), bitset);
+    println!("HashSet<u8>   {:>10.2?}", hashset_time);
+    println!("ItemSet       {:>10.2?}", bitset_time);
+    println!(
+        "speed-up      {:>9.1}x",
+        hashset_time.as_secs_f64() / bitset_time.as_secs_f64()
+    );
+    println!("checked       {:>10.2?}", checked_time);
+    println!("{:<2} threads    {:>10.2?}", threads, parallel_time);
+    println!(
+        "speed-up      {:>9.1}x",
+        checked_time.as_secs_f64() / parallel_time.as_secs_f64()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::{find_duplicate_hashset, find_duplicate_three_hashset};
+    use crate::generate::generate_rucksacks;
+    use crate::{find_common_item, find_duplicate, split_into_compartments};
+
+    #[test]
+    fn test_implementations_agree() {
+        let lines = generate_rucksacks(100, 11);
+        for line in &lines {
+            let (a, b) = split_into_compartments(line.as_bytes());
+            assert_eq!(find_duplicate_hashset(a, b), find_duplicate(a, b));
+        }
+        for group in lines.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| group[i].as_bytes());
+            assert_eq!(
+                find_duplicate_three_hashset(a, b, c),
+                find_common_item(&[a, b, c])
+            );
+        }
+    }
+}
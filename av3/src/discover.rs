@@ -0,0 +1,211 @@
+use crate::item_set::ItemSet;
+
+#[derive(Debug, PartialEq)]
+pub enum NoPartition {
+    /// The number of rucksacks is not a multiple of the group size.
+    Incomplete { len: usize, size: usize },
+    /// The rucksack on this 1-based line is in no group with a single badge.
+    NoGroup { line: usize },
+    /// Every way of combining the possible groups was tried without success.
+    Exhausted,
+}
+
+impl std::fmt::Display for NoPartition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoPartition::Incomplete { len, size } => write!(
+                f,
+                "{} rucksacks cannot be split into groups of {}",
+                len, size
+            ),
+            NoPartition::NoGroup { line } => {
+                write!(f, "line {} fits in no group with a single badge", line)
+            }
+            NoPartition::Exhausted => write!(f, "no grouping gives every group a single badge"),
+        }
+    }
+}
+
+/// Every set of `group_size` rucksacks sharing exactly one item, members in increasing order.
+fn candidate_groups(items: &[ItemSet], group_size: usize) -> Vec<Vec<usize>> {
+    fn extend(
+        items: &[ItemSet],
+        group_size: usize,
+        group: &mut Vec<usize>,
+        common: ItemSet,
+        groups: &mut Vec<Vec<usize>>,
+    ) {
+        if group.len() == group_size {
+            if common.len() == 1 {
+                groups.push(group.clone());
+            }
+            return;
+        }
+        let start = group.last().map_or(0, |last| last + 1);
+        for index in start..items.len() {
+            let shared = common.intersection(&items[index]);
+            // adding members only shrinks the common items
+            if shared.len() == 0 {
+                continue;
+            }
+            group.push(index);
+            extend(items, group_size, group, shared, groups);
+            group.pop();
+        }
+    }
+
+    let mut groups = vec![];
+    for (first, items_first) in items.iter().enumerate() {
+        extend(
+            items,
+            group_size,
+            &mut vec![first],
+            *items_first,
+            &mut groups,
+        );
+    }
+    groups
+}
+
+struct Search<'a> {
+    groups: &'a [Vec<usize>],
+    /// The candidate groups each rucksack belongs to.
+    memberships: Vec<Vec<usize>>,
+    used: Vec<bool>,
+    chosen: Vec<usize>,
+}
+
+impl Search<'_> {
+    fn is_free(&self, group: usize) -> bool {
+        self.groups[group].iter().all(|&member| !self.used[member])
+    }
+
+    /// Covers the ungrouped rucksack with the fewest free candidate groups first, so dead ends
+    /// are found as early as possible; true once everyone is grouped.
+    fn solve(&mut self) -> bool {
+        let next = (0..self.used.len())
+            .filter(|&index| !self.used[index])
+            .map(|index| {
+                let free: Vec<usize> = self.memberships[index]
+                    .iter()
+                    .copied()
+                    .filter(|&group| self.is_free(group))
+                    .collect();
+                free
+            })
+            .min_by_key(|free| free.len());
+        let Some(options) = next else {
+            return true;
+        };
+
+        for group in options {
+            self.groups[group]
+                .iter()
+                .for_each(|&member| self.used[member] = true);
+            self.chosen.push(group);
+            if self.solve() {
+                return true;
+            }
+            self.chosen.pop();
+            self.groups[group]
+                .iter()
+                .for_each(|&member| self.used[member] = false);
+        }
+        false
+    }
+}
+
+/// Partitions shuffled rucksacks into groups of `group_size` that each share exactly one badge,
+/// returning the 0-based indices of every group.
+///
+/// All groups with a single badge are listed first, then an exact cover of the rucksacks is
+/// searched by backtracking over them. The search is exhaustive, so `Exhausted` means no
+/// partition exists.
+pub fn discover_groups(
+    rucksacks: &[&[u8]],
+    group_size: usize,
+) -> Result<Vec<Vec<usize>>, NoPartition> {
+    assert!(group_size > 0, "Group size should be at least 1");
+    if !rucksacks.len().is_multiple_of(group_size) {
+        return Err(NoPartition::Incomplete {
+            len: rucksacks.len(),
+            size: group_size,
+        });
+    }
+    let items: Vec<ItemSet> = rucksacks.iter().map(|r| ItemSet::from(*r)).collect();
+    let groups = candidate_groups(&items, group_size);
+
+    let mut memberships = vec![vec![]; items.len()];
+    for (index, group) in groups.iter().enumerate() {
+        group
+            .iter()
+            .for_each(|&member| memberships[member].push(index));
+    }
+    if let Some(index) = memberships.iter().position(|groups| groups.is_empty()) {
+        return Err(NoPartition::NoGroup { line: index + 1 });
+    }
+
+    let mut search = Search {
+        groups: &groups,
+        memberships,
+        used: vec![false; items.len()],
+        chosen: vec![],
+    };
+    if search.solve() {
+        let mut chosen: Vec<Vec<usize>> = search
+            .chosen
+            .iter()
+            .map(|&group| groups[group].clone())
+            .collect();
+        chosen.sort();
+        Ok(chosen)
+    } else {
+        Err(NoPartition::Exhausted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discover::{discover_groups, NoPartition};
+    use crate::find_common_item;
+    use crate::generate::{generate_rucksacks, XorShift};
+
+    #[test]
+    fn test_finds_shuffled_groups() {
+        let lines = generate_rucksacks(20, 5);
+        let mut shuffled: Vec<&[u8]> = lines.iter().map(|line| line.as_bytes()).collect();
+        XorShift::new(9).shuffle(&mut shuffled);
+
+        let groups = discover_groups(&shuffled, 3).unwrap();
+        assert_eq!(20, groups.len());
+        let mut seen: Vec<usize> = groups.iter().flatten().copied().collect();
+        seen.sort();
+        assert_eq!((0..60).collect::<Vec<_>>(), seen);
+        for group in &groups {
+            let members: Vec<&[u8]> = group.iter().map(|&i| shuffled[i]).collect();
+            assert!(find_common_item(&members).is_some());
+        }
+    }
+
+    #[test]
+    fn test_no_partition() {
+        assert_eq!(
+            Err(NoPartition::Incomplete { len: 4, size: 3 }),
+            discover_groups(&[b"ab", b"bc", b"cd", b"da"], 3)
+        );
+        // the only possible group shares both a and c
+        assert_eq!(
+            Err(NoPartition::NoGroup { line: 1 }),
+            discover_groups(&[b"ac", b"abc", b"cad"], 3)
+        );
+        // each item makes one of four possible groups, but any two of them overlap
+        assert_eq!(
+            Err(NoPartition::Exhausted),
+            discover_groups(&[b"pq", b"pr", b"ps", b"qr", b"qs", b"rs"], 3)
+        );
+        assert_eq!(
+            Ok(vec![vec![0, 1, 2]]),
+            discover_groups(&[b"ac", b"abc", b"cbd"], 3)
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use crate::get_item;
+
+/// Small xorshift generator so generated inputs are reproducible without extra dependencies.
+pub struct XorShift(u64);
+
+impl XorShift {
+    pub fn new(seed: u64) -> Self {
+        XorShift(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Generates `groups` groups of three valid rucksacks.
+///
+/// The two compartments of every rucksack share exactly one item and every group shares exactly
+/// one badge: each elf of a group draws from its own pool of 17 items, and only the badge is
+/// given to all three.
+pub fn generate_rucksacks(groups: usize, seed: u64) -> Vec<String> {
+    let mut rng = XorShift::new(seed);
+    let mut lines = Vec::with_capacity(groups * 3);
+
+    for _ in 0..groups {
+        let mut items: Vec<u8> = (1..=52).map(get_item).collect();
+        rng.shuffle(&mut items);
+        let badge = items[0];
+
+        for pool in items[1..].chunks(17) {
+            let duplicate = pool[0];
+            let (left, right) = pool[1..].split_at(8);
+            let len = 4 + rng.below(13);
+
+            let mut first: Vec<u8> = vec![duplicate];
+            let mut second: Vec<u8> = vec![duplicate, badge];
+            first.extend((1..len).map(|_| left[rng.below(left.len())]));
+            second.extend((2..len).map(|_| right[rng.below(right.len())]));
+            if rng.below(2) == 0 {
+                std::mem::swap(&mut first, &mut second);
+            }
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+
+            lines.push(first.into_iter().chain(second).map(char::from).collect());
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::generate_rucksacks;
+    use crate::item_set::ItemSet;
+    use crate::split_into_compartments;
+
+    #[test]
+    fn test_generated_input_is_valid() {
+        let lines = generate_rucksacks(50, 3);
+        assert_eq!(150, lines.len());
+        assert_eq!(lines, generate_rucksacks(50, 3));
+
+        for line in &lines {
+            let (a, b) = split_into_compartments(line.as_bytes());
+            assert_eq!(
+                1,
+                ItemSet::from(a).intersection(&ItemSet::from(b)).len(),
+                "{}",
+                line
+            );
+        }
+        for group in lines.chunks(3) {
+            let badges = ItemSet::from(group[0].as_bytes())
+                .intersection(&ItemSet::from(group[1].as_bytes()))
+                .intersection(&ItemSet::from(group[2].as_bytes()));
+            assert_eq!(1, badges.len());
+        }
+    }
+}
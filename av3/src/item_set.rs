@@ -0,0 +1,88 @@
+use crate::{get_item, get_item_value};
+
+/// A set of rucksack items stored as a bitmask, bit `n` standing for the item with priority `n`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn insert(&mut self, item: u8) {
+        self.0 |= 1 << get_item_value(item);
+    }
+
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// The item with the highest priority.
+    pub fn max(&self) -> Option<u8> {
+        match self.0 {
+            0 => None,
+            bits => Some(get_item(63 - bits.leading_zeros() as u8)),
+        }
+    }
+
+    /// Iterates the items in order of increasing priority.
+    pub fn iter(&self) -> impl Iterator<Item = u8> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let value = bits.trailing_zeros() as u8;
+            bits &= bits - 1;
+            Some(get_item(value))
+        })
+    }
+}
+
+impl FromIterator<u8> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut set = ItemSet::default();
+        for item in iter {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+impl From<&[u8]> for ItemSet {
+    fn from(items: &[u8]) -> Self {
+        items.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::item_set::ItemSet;
+
+    #[test]
+    fn test_set_operations() {
+        let a = ItemSet::from(b"abcZ".as_slice());
+        let b = ItemSet::from(b"cZZx".as_slice());
+
+        assert_eq!(
+            vec![b'c', b'Z'],
+            a.intersection(&b).iter().collect::<Vec<_>>()
+        );
+        assert_eq!(5, a.union(&b).len());
+        assert_eq!(Some(b'Z'), a.max());
+        assert_eq!(None, ItemSet::default().max());
+        assert_eq!(0, a.intersection(&ItemSet::from(b"XYz".as_slice())).len());
+    }
+
+    #[test]
+    fn test_iter_covers_all_items() {
+        let items: Vec<u8> = (b'a'..=b'z').chain(b'A'..=b'Z').collect();
+        let set = ItemSet::from(items.as_slice());
+        assert_eq!(52, set.len());
+        assert_eq!(items, set.iter().collect::<Vec<_>>());
+    }
+}
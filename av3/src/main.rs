@@ -1,64 +1,614 @@
-use std::collections::HashSet;
-use std::fs;
+mod analytics;
+mod benchmark;
+mod discover;
+mod generate;
+mod item_set;
+mod parallel;
+mod priority;
+mod repack;
+mod trace;
+mod validate;
 
-fn split_into_compartments(rucksack: &str) -> (&str, &str) {
+use std::{env, fs, process, str::FromStr};
+
+use item_set::ItemSet;
+use priority::PriorityScheme;
+
+fn split_into_compartments(rucksack: &[u8]) -> (&[u8], &[u8]) {
     let len = rucksack.len();
-    assert!(len % 2 == 0);
+    assert!(len.is_multiple_of(2));
     let middle = len / 2;
-    return (&rucksack[..middle], &rucksack[middle..]);
+    (&rucksack[..middle], &rucksack[middle..])
+}
+
+/// Every item found in both compartments.
+fn find_duplicates(a: &[u8], b: &[u8]) -> ItemSet {
+    ItemSet::from(a).intersection(&ItemSet::from(b))
 }
 
-fn find_duplicate(a: &str, b: &str) -> Option<char> {
-    let items_a: HashSet<char> = a.chars().collect::<HashSet<char>>();
-    let items_b: HashSet<char> = b.chars().collect::<HashSet<char>>();
-    let intersection = items_a.intersection(&items_b);
+/// The shared item of both compartments, the highest priority one if there are several.
+fn find_duplicate(a: &[u8], b: &[u8]) -> Option<u8> {
+    find_duplicates(a, b).max()
+}
+
+/// Finds an item carried by every rucksack of the group, the highest priority one if there
+/// are several.
+fn find_common_item(group: &[&[u8]]) -> Option<u8> {
+    let (first, rest) = group.split_first()?;
+    rest.iter()
+        .fold(ItemSet::from(*first), |common, rucksack| {
+            common.intersection(&ItemSet::from(*rucksack))
+        })
+        .max()
+}
 
-    return intersection.map(|item| item.clone()).next();
+/// Sums the priorities of the badge of every group of `group_size` consecutive rucksacks.
+///
+/// The rucksacks should have been validated, so every group is complete and has a badge.
+fn badge_sum(rucksacks: &[&[u8]], group_size: usize) -> u32 {
+    assert!(group_size > 0, "Group size should be at least 1");
+    assert!(
+        rucksacks.len().is_multiple_of(group_size),
+        "Should have been validated"
+    );
+    rucksacks
+        .chunks(group_size)
+        .map(|group| find_common_item(group).expect("Should have been validated"))
+        .map(|c| get_item_value(c) as u32)
+        .sum()
 }
 
-fn find_duplicate_three(a: &str, b: &str, c: &str) -> Option<char> {
-    let items_a: HashSet<char> = a.chars().collect::<HashSet<char>>();
-    let items_b: HashSet<char> = b.chars().collect::<HashSet<char>>();
-    let items_c: HashSet<char> = c.chars().collect::<HashSet<char>>();
+/// Sums the priorities of the shared item of every rucksack.
+fn rucksack_sum(rucksacks: &[&[u8]]) -> u32 {
+    rucksacks
+        .iter()
+        .map(|rucksack| split_into_compartments(rucksack))
+        .map(|(com_a, com_b)| find_duplicate(com_a, com_b).unwrap())
+        .map(|c| get_item_value(c) as u32)
+        .sum()
+}
 
-    let intersection_a_b = items_a
-        .intersection(&items_b)
-        .map(|x| x.clone())
-        .collect::<HashSet<char>>();
-    let intersection_a_b_c = intersection_a_b.intersection(&items_c);
+/// The rucksack value sum and the elf badge sum.
+fn sums(rucksacks: &[&[u8]], group_size: usize) -> (u32, u32) {
+    (rucksack_sum(rucksacks), badge_sum(rucksacks, group_size))
+}
 
-    return intersection_a_b_c.map(|item| item.clone()).next();
+/// The sums after checking the rucksacks with the puzzle rules, or every issue that stops them.
+fn checked_sums(
+    rucksacks: &[&[u8]],
+    group_size: usize,
+    allow_ambiguous: bool,
+) -> Result<(u32, u32), Vec<validate::Issue>> {
+    let fatal_issues = fatal_issues(
+        validate::validate_ascii(rucksacks, group_size),
+        allow_ambiguous,
+    );
+    if !fatal_issues.is_empty() {
+        return Err(fatal_issues);
+    }
+    Ok(sums(rucksacks, group_size))
 }
 
-fn get_item_value(c: char) -> u8 {
+fn get_item_value(c: u8) -> u8 {
     if c.is_ascii_lowercase() {
-        return c as u8 - 96;
+        c - 96
     } else if c.is_ascii_uppercase() {
-        return c as u8 - 38;
+        c - 38
     } else {
         panic!("Invalid item");
     }
 }
 
+/// The item with the given priority, the inverse of `get_item_value`.
+fn get_item(value: u8) -> u8 {
+    match value {
+        1..=26 => value + 96,
+        27..=52 => value + 38,
+        _ => panic!("Invalid item value"),
+    }
+}
+
+/// The first line holding a character outside ASCII, which the byte-based finders cannot split.
+#[derive(Debug, PartialEq)]
+struct NonAsciiLine {
+    line: usize,
+    item: char,
+}
+
+impl std::fmt::Display for NonAsciiLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: non-ASCII item {:?}", self.line, self.item)
+    }
+}
+
+/// Checks that every line is ASCII before handing out its bytes, so compartments split by byte
+/// length always hold whole items.
+fn as_ascii_lines(content: &str) -> Result<Vec<&[u8]>, NonAsciiLine> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| match line.chars().find(|c| !c.is_ascii()) {
+            Some(item) => Err(NonAsciiLine {
+                line: index + 1,
+                item,
+            }),
+            None => Ok(line.as_bytes()),
+        })
+        .collect()
+}
+
+/// Returns the value following `--name` on the command line, if present.
+fn get_option<T: FromStr>(args: &[String], name: &str) -> Option<T> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("Invalid value {:?} for {}", value, name))
+        })
+}
+
+fn run_benchmark(args: &[String]) {
+    let groups: usize = get_option(args, "--groups").unwrap_or(1_000_000);
+    let seed: u64 = get_option(args, "--seed").unwrap_or(2022);
+
+    let lines = generate::generate_rucksacks(groups, seed);
+    let lines: Vec<&[u8]> = lines.iter().map(|line| line.as_bytes()).collect();
+    let threads = get_option(args, "--threads").map_or_else(available_threads, worker_count);
+    benchmark::run_benchmark(&lines, threads);
+}
+
+fn available_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+/// The requested number of threads, with a warning when it is more than the machine can run at
+/// once; the extra threads still run, they just take turns.
+fn worker_count(requested: usize) -> usize {
+    let available = available_threads();
+    if requested > available {
+        eprintln!(
+            "Warning: {} threads requested but only {} can run at once",
+            requested, available
+        );
+    }
+    requested
+}
+
+fn has_flag(args: &[String], name: &str) -> bool {
+    args.iter().any(|arg| arg == name)
+}
+
+/// Reads the rucksack list given with `--input`, or `./rucksack.txt`.
+fn read_input(args: &[String]) -> String {
+    let file_path: String = get_option(args, "--input").unwrap_or("./rucksack.txt".to_string());
+    fs::read_to_string(file_path).expect("Should have been able to read the file")
+}
+
+/// The lines of the input as ASCII bytes, exiting if they are not.
+fn read_ascii_lines(file_content: &str) -> Vec<&[u8]> {
+    as_ascii_lines(file_content).unwrap_or_else(|err| {
+        eprintln!("The rucksack list is not ASCII: {}", err);
+        process::exit(1);
+    })
+}
+
+/// The lines of the input as ASCII bytes, exiting if any of them cannot be an item list.
+fn read_rucksacks(file_content: &str, group_size: usize) -> Vec<&[u8]> {
+    let lines = read_ascii_lines(file_content);
+    let malformed: Vec<validate::Issue> = validate::validate_ascii(&lines, group_size)
+        .into_iter()
+        .filter(|issue| issue.problem.is_malformed())
+        .collect();
+    if !malformed.is_empty() {
+        eprintln!("The rucksack list is invalid:");
+        validate::print_issues(&malformed);
+        process::exit(1);
+    }
+    lines
+}
+
+/// The issues that stop the sums from being computed.
+fn fatal_issues(issues: Vec<validate::Issue>, allow_ambiguous: bool) -> Vec<validate::Issue> {
+    issues
+        .into_iter()
+        .filter(|issue| issue.problem.is_fatal(allow_ambiguous))
+        .collect()
+}
+
+fn exit_invalid(fatal_issues: &[validate::Issue], allow_ambiguous: bool) -> ! {
+    eprintln!("The rucksack list is invalid:");
+    validate::print_issues(fatal_issues);
+    if !allow_ambiguous
+        && fatal_issues
+            .iter()
+            .any(|issue| issue.problem.is_ambiguous())
+    {
+        eprintln!("Use --allow-ambiguous to pick the highest priority of several shared items");
+    }
+    process::exit(1);
+}
+
+fn get_group_size(args: &[String]) -> usize {
+    let group_size: usize = get_option(args, "--group-size").unwrap_or(3);
+    if group_size == 0 {
+        eprintln!("--group-size should be at least 1");
+        process::exit(1);
+    }
+    group_size
+}
+
+/// The scheme given with `--priorities`, or the puzzle rules.
+fn get_scheme(args: &[String]) -> Box<dyn PriorityScheme> {
+    let priorities: String = get_option(args, "--priorities").unwrap_or("puzzle".to_string());
+    priority::scheme_from_arg(&priorities).unwrap_or_else(|err| {
+        eprintln!("Invalid --priorities: {}", err);
+        process::exit(1);
+    })
+}
+
+/// Whether `--priorities` is absent or picks the puzzle rules.
+fn is_puzzle_scheme(args: &[String]) -> bool {
+    get_option::<String>(args, "--priorities").is_none_or(|p| p == "puzzle")
+}
+
+/// Exits if another priority scheme is chosen for `feature`, which only knows the puzzle rules.
+fn require_puzzle_scheme(args: &[String], feature: &str) {
+    if !is_puzzle_scheme(args) {
+        eprintln!("{} only supports the puzzle priorities", feature);
+        process::exit(1);
+    }
+}
+
+fn run_validate(args: &[String]) {
+    let file_content = read_input(args);
+    let group_size = get_group_size(args);
+    let scheme = get_scheme(args);
+
+    let (checked, issues) = if is_puzzle_scheme(args) {
+        let rucksack_description = read_ascii_lines(&file_content);
+        let issues = validate::validate_ascii(&rucksack_description, group_size);
+        (rucksack_description.len(), issues)
+    } else {
+        let rucksack_description: Vec<&str> = file_content.lines().collect();
+        let issues = validate::validate(&rucksack_description, group_size, scheme.as_ref());
+        (rucksack_description.len(), issues)
+    };
+    validate::print_issues(&issues);
+    println!(
+        "{} rucksacks checked, {} problems found",
+        checked,
+        issues.len()
+    );
+    if !issues.is_empty() {
+        process::exit(1);
+    }
+}
+
+fn run_shared(args: &[String]) {
+    let file_content = read_input(args);
+    let scheme = get_scheme(args);
+
+    let fatal_problem = |issues: Vec<validate::Issue>| {
+        issues
+            .into_iter()
+            .find(|issue| issue.problem.is_fatal(true))
+            .map_or(Ok(()), |issue| Err(issue.problem))
+    };
+    // every shared item with its priority, or the problem that stops the line from being split
+    let shared: Vec<Result<Vec<(u32, char)>, validate::Problem>> = if is_puzzle_scheme(args) {
+        read_ascii_lines(&file_content)
+            .into_iter()
+            .map(|rucksack| {
+                fatal_problem(validate::validate_ascii(&[rucksack], 1))?;
+                let (com_a, com_b) = split_into_compartments(rucksack);
+                Ok(find_duplicates(com_a, com_b)
+                    .iter()
+                    .map(|c| (get_item_value(c) as u32, char::from(c)))
+                    .collect())
+            })
+            .collect()
+    } else {
+        file_content
+            .lines()
+            .map(|rucksack| {
+                fatal_problem(validate::validate(&[rucksack], 1, scheme.as_ref()))?;
+                let items: Vec<char> = rucksack.chars().collect();
+                let (com_a, com_b) = items.split_at(items.len() / 2);
+                Ok(priority::common_items(&[com_a, com_b], scheme.as_ref())
+                    .into_iter()
+                    .collect())
+            })
+            .collect()
+    };
+
+    let mut ambiguous = 0;
+    for (index, duplicates) in shared.into_iter().enumerate() {
+        let duplicates = match duplicates {
+            Ok(duplicates) => duplicates,
+            Err(problem) => {
+                println!("line {}: {}", index + 1, problem);
+                continue;
+            }
+        };
+        let items: Vec<String> = duplicates
+            .iter()
+            .map(|(value, c)| format!("{}({})", c, value))
+            .collect();
+        if duplicates.len() > 1 {
+            ambiguous += 1;
+        }
+        println!(
+            "line {}: {}{}",
+            index + 1,
+            items.join(" "),
+            if duplicates.len() > 1 {
+                "  AMBIGUOUS"
+            } else {
+                ""
+            }
+        );
+    }
+    println!("{} ambiguous rucksacks", ambiguous);
+}
+
+fn run_repack(args: &[String]) {
+    require_puzzle_scheme(args, "repack");
+    let file_content = read_input(args);
+
+    let mut failed = 0;
+    for (index, rucksack) in read_ascii_lines(&file_content).into_iter().enumerate() {
+        let issues = validate::validate_ascii(&[rucksack], 1);
+        if let Some(issue) = issues.iter().find(|issue| issue.problem.is_malformed()) {
+            println!("line {}: {}", index + 1, issue.problem);
+            failed += 1;
+            continue;
+        }
+        let Some(plan) = repack::plan_repack(rucksack) else {
+            println!("line {}: cannot be repacked", index + 1);
+            failed += 1;
+            continue;
+        };
+        // re-run the finder on the result rather than trusting the plan
+        let (com_a, com_b) = split_into_compartments(plan.repacked.as_bytes());
+        if let Some(item) = find_duplicate(com_a, com_b) {
+            println!(
+                "line {}: {} still shares {}",
+                index + 1,
+                plan.repacked,
+                item as char
+            );
+            failed += 1;
+            continue;
+        }
+        let moves: Vec<String> = plan.moves.iter().map(|m| m.to_string()).collect();
+        println!(
+            "line {}: {} -> {} ({} moves: {})",
+            index + 1,
+            String::from_utf8_lossy(rucksack),
+            plan.repacked,
+            moves.len(),
+            moves.join(" ")
+        );
+    }
+    if failed > 0 {
+        println!("{} rucksacks could not be repacked", failed);
+        process::exit(1);
+    }
+}
+
+fn run_groups(args: &[String]) {
+    require_puzzle_scheme(args, "groups");
+    let file_content = read_input(args);
+    let group_size = get_group_size(args);
+    let rucksack_description = read_rucksacks(&file_content, group_size);
+
+    match discover::discover_groups(&rucksack_description, group_size) {
+        Ok(groups) => {
+            let mut badge_sum = 0;
+            for group in &groups {
+                let members: Vec<&[u8]> = group.iter().map(|&i| rucksack_description[i]).collect();
+                let badge = find_common_item(&members).unwrap();
+                badge_sum += get_item_value(badge) as u32;
+                let lines: Vec<String> = group.iter().map(|i| (i + 1).to_string()).collect();
+                println!(
+                    "lines {}: badge {}({})",
+                    lines.join(" "),
+                    badge as char,
+                    get_item_value(badge)
+                );
+            }
+            println!("{:?} elf badge sum", badge_sum);
+        }
+        Err(err) => {
+            println!("No valid grouping: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_analytics(args: &[String]) {
+    require_puzzle_scheme(args, "analytics");
+    let file_content = read_input(args);
+    let group_size = get_group_size(args);
+    let rucksack_description = read_rucksacks(&file_content, group_size);
+
+    let stats = analytics::item_stats(&rucksack_description, group_size);
+    analytics::print_table(&stats);
+    if let Some(csv_path) = get_option::<String>(args, "--csv") {
+        let mut file =
+            fs::File::create(&csv_path).expect("Should have been able to create the file");
+        analytics::write_csv(&stats, &mut file).expect("Should have been able to write the file");
+        println!("Wrote {}", csv_path);
+    }
+}
+
 fn main() {
-    let file_path = "./rucksack.txt";
-    let file_content =
-        fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let rucksack_description: Vec<&str> = file_content.lines().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("benchmark") => return run_benchmark(&args[1..]),
+        Some("validate") => return run_validate(&args[1..]),
+        Some("shared") => return run_shared(&args[1..]),
+        Some("repack") => return run_repack(&args[1..]),
+        Some("groups") => return run_groups(&args[1..]),
+        Some("analytics") => return run_analytics(&args[1..]),
+        _ => {}
+    }
 
-    let rucksack_value_sum: u32 = rucksack_description
-        .iter()
-        .map(|rucksack| split_into_compartments(&rucksack))
-        .map(|(com_a, com_b)| find_duplicate(com_a, com_b).unwrap())
-        .map(|c| get_item_value(c) as u32)
-        .sum();
+    let file_content = read_input(&args);
+    let group_size = get_group_size(&args);
+    let allow_ambiguous = has_flag(&args, "--allow-ambiguous");
+    let is_puzzle = is_puzzle_scheme(&args);
+    let scheme = get_scheme(&args);
+    let trace = has_flag(&args, "--trace");
+    if trace {
+        require_puzzle_scheme(&args, "--trace");
+    }
 
-    let elf_badge_value_sum: u32 = rucksack_description
-        .chunks(3)
-        .map(|x| find_duplicate_three(x[0], x[1], x[2]).unwrap())
-        .map(|c| get_item_value(c) as u32)
-        .sum();
+    if !is_puzzle {
+        // other schemes may use any Unicode character as an item, so they keep the char checks
+        let rucksack_description: Vec<&str> = file_content.lines().collect();
+        let fatal_issues = fatal_issues(
+            validate::validate(&rucksack_description, group_size, scheme.as_ref()),
+            allow_ambiguous,
+        );
+        if !fatal_issues.is_empty() {
+            exit_invalid(&fatal_issues, allow_ambiguous);
+        }
+        let (rucksack_value_sum, elf_badge_value_sum) =
+            priority::priority_sums(&rucksack_description, group_size, scheme.as_ref());
+        println!("{:?} rucksack value sum", rucksack_value_sum);
+        println!("{:?} elf badge sum", elf_badge_value_sum);
+        return;
+    }
+
+    // the puzzle rules keep the ItemSet fast path over bytes
+    let rucksack_description = read_ascii_lines(&file_content);
+    let threads = get_option(&args, "--threads").map_or(1, worker_count);
+    let sums = if threads > 1 {
+        parallel::parallel_sums(&rucksack_description, group_size, threads, allow_ambiguous)
+    } else {
+        checked_sums(&rucksack_description, group_size, allow_ambiguous)
+    };
+    let (rucksack_value_sum, elf_badge_value_sum) =
+        sums.unwrap_or_else(|issues| exit_invalid(&issues, allow_ambiguous));
+    if trace {
+        trace::write_trace(&rucksack_description, group_size, &mut std::io::stdout())
+            .expect("Should have been able to write the trace");
+        return;
+    }
 
     println!("{:?} rucksack value sum", rucksack_value_sum);
     println!("{:?} elf badge sum", elf_badge_value_sum);
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        as_ascii_lines, badge_sum, find_common_item, find_duplicate, find_duplicates,
+        generate::generate_rucksacks, get_item, get_item_value, priority, split_into_compartments,
+        NonAsciiLine,
+    };
+
+    const EXAMPLE: [&[u8]; 6] = [
+        b"vJrwpWtwJgWrhcsFMMfFFhFp",
+        b"jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+        b"PmmdzqPrVvPwwTWBwg",
+        b"wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+        b"ttgJtRGJQctTZtZT",
+        b"CrZsJsPPZsGzwwsLwLmpwMDw",
+    ];
+
+    #[test]
+    fn test_find_duplicate() {
+        let duplicates: Vec<u8> = EXAMPLE
+            .iter()
+            .map(|rucksack| split_into_compartments(rucksack))
+            .map(|(a, b)| find_duplicate(a, b).unwrap())
+            .collect();
+        assert_eq!(b"pLPvts", duplicates.as_slice());
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let duplicates = find_duplicates(b"abcZ", b"Zxba");
+        assert_eq!(b"abZ", duplicates.iter().collect::<Vec<_>>().as_slice());
+        // whatever the order of the items, the highest priority wins
+        assert_eq!(Some(b'Z'), find_duplicate(b"abcZ", b"Zxba"));
+        assert_eq!(Some(b'Z'), find_duplicate(b"Zcba", b"abxZ"));
+        assert_eq!(None, find_duplicate(b"ab", b"cd"));
+    }
+
+    #[test]
+    fn test_find_common_item() {
+        assert_eq!(Some(b'r'), find_common_item(&EXAMPLE[..3]));
+        assert_eq!(Some(b'Z'), find_common_item(&EXAMPLE[3..]));
+        // a single rucksack shares all of its items, the highest priority one is picked
+        assert_eq!(Some(b'Z'), find_common_item(&EXAMPLE[3..4]));
+        assert_eq!(None, find_common_item(&EXAMPLE));
+        assert_eq!(None, find_common_item(&[]));
+    }
+
+    #[test]
+    fn test_badge_sum() {
+        assert_eq!(70, badge_sum(&EXAMPLE, 3));
+        // a single rucksack is its own badge group, the highest priority item wins
+        assert_eq!(
+            EXAMPLE
+                .iter()
+                .map(|rucksack| get_item_value(find_common_item(&[rucksack]).unwrap()) as u32)
+                .sum::<u32>(),
+            badge_sum(&EXAMPLE, 1)
+        );
+    }
+
+    #[test]
+    fn test_item_values() {
+        assert_eq!(16, get_item_value(b'p'));
+        assert_eq!(38, get_item_value(b'L'));
+        for value in 1..=52 {
+            assert_eq!(value, get_item_value(get_item(value)));
+        }
+    }
+
+    #[test]
+    fn test_non_ascii_lines_are_rejected() {
+        assert_eq!(
+            Err(NonAsciiLine {
+                line: 2, item: 'é'
+            }),
+            as_ascii_lines("abab\naéaé\n")
+        );
+        assert_eq!(Ok(vec![b"ab".as_slice(), b"cd"]), as_ascii_lines("ab\ncd"));
+    }
+
+    #[test]
+    fn test_bytes_agree_with_chars() {
+        let content = generate_rucksacks(200, 43).join("\n");
+        let lines: Vec<&str> = content.lines().collect();
+        let bytes = as_ascii_lines(&content).unwrap();
+
+        for (line, rucksack) in lines.iter().zip(&bytes) {
+            let items: Vec<char> = line.chars().collect();
+            let (a, b) = items.split_at(items.len() / 2);
+            let by_chars = priority::common_items(&[a, b], &priority::Puzzle);
+            let (a, b) = split_into_compartments(rucksack);
+            let by_bytes: Vec<char> = find_duplicates(a, b).iter().map(char::from).collect();
+            assert_eq!(
+                by_chars.iter().map(|(_, c)| *c).collect::<Vec<_>>(),
+                by_bytes
+            );
+        }
+        let badges = badge_sum(&bytes, 3);
+        let duplicates: u32 = bytes
+            .iter()
+            .map(|rucksack| split_into_compartments(rucksack))
+            .map(|(a, b)| get_item_value(find_duplicate(a, b).unwrap()) as u32)
+            .sum();
+        assert_eq!(
+            priority::priority_sums(&lines, 3, &priority::Puzzle),
+            (duplicates, badges)
+        );
+    }
+}
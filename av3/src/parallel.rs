@@ -0,0 +1,115 @@
+use std::thread;
+
+use crate::checked_sums;
+use crate::validate::Issue;
+
+/// Checks the rucksacks and computes the same sums as `checked_sums`, with the rucksacks split
+/// over at most `threads` threads and never more threads than groups.
+///
+/// Every chunk holds a whole number of groups so no badge group straddles two threads. Chunks are
+/// merged in input order, so the issues keep the lines and the order the sequential path reports.
+pub fn parallel_sums(
+    rucksacks: &[&[u8]],
+    group_size: usize,
+    threads: usize,
+    allow_ambiguous: bool,
+) -> Result<(u32, u32), Vec<Issue>> {
+    assert!(group_size > 0, "Group size should be at least 1");
+    let groups = rucksacks.len().div_ceil(group_size);
+    let workers = threads.clamp(1, groups.max(1));
+    let chunk_len = groups.div_ceil(workers).max(1) * group_size;
+
+    let results: Vec<Result<(u32, u32), Vec<Issue>>> = thread::scope(|scope| {
+        let handles: Vec<_> = rucksacks
+            .chunks(chunk_len)
+            .map(|chunk| scope.spawn(move || checked_sums(chunk, group_size, allow_ambiguous)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Worker thread should not panic"))
+            .collect()
+    });
+
+    let mut total = (0, 0);
+    let mut issues = vec![];
+    for (index, result) in results.into_iter().enumerate() {
+        let offset = index * chunk_len;
+        match result {
+            Ok((rucksack_value_sum, elf_badge_value_sum)) => {
+                total.0 += rucksack_value_sum;
+                total.1 += elf_badge_value_sum;
+            }
+            Err(chunk_issues) => issues.extend(chunk_issues.into_iter().map(|issue| Issue {
+                line: issue.line + offset,
+                problem: issue.problem,
+            })),
+        }
+    }
+    if issues.is_empty() {
+        Ok(total)
+    } else {
+        Err(issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checked_sums;
+    use crate::generate::generate_rucksacks;
+    use crate::parallel::parallel_sums;
+    use crate::validate::{Issue, Problem};
+
+    #[test]
+    fn test_matches_sequential() {
+        let lines = generate_rucksacks(101, 17);
+        let lines: Vec<&[u8]> = lines.iter().map(|line| line.as_bytes()).collect();
+
+        for threads in [1, 2, 3, 8, 500] {
+            assert_eq!(
+                checked_sums(&lines, 3, false),
+                parallel_sums(&lines, 3, threads, false)
+            );
+            // every single rucksack is its own group with several common items
+            assert_eq!(
+                checked_sums(&lines, 1, true),
+                parallel_sums(&lines, 1, threads, true)
+            );
+        }
+    }
+
+    #[test]
+    fn test_issues_keep_their_line() {
+        let lines = generate_rucksacks(10, 2);
+        let mut lines: Vec<&[u8]> = lines.iter().map(|line| line.as_bytes()).collect();
+        let missing = (b'a'..=b'z').find(|c| !lines[24].contains(c)).unwrap();
+        let lonely = [missing, missing];
+        lines[25] = &lonely;
+        lines[7] = b"abc";
+
+        assert_eq!(
+            Err(vec![
+                Issue {
+                    line: 7,
+                    problem: Problem::NoBadge
+                },
+                Issue {
+                    line: 8,
+                    problem: Problem::OddLength(3)
+                },
+                Issue {
+                    line: 25,
+                    problem: Problem::NoBadge
+                },
+            ]),
+            parallel_sums(&lines, 3, 4, false)
+        );
+        assert_eq!(
+            checked_sums(&lines, 3, false),
+            parallel_sums(&lines, 3, 4, false)
+        );
+        assert_eq!(
+            checked_sums(&lines[..5], 3, false),
+            parallel_sums(&lines[..5], 3, 2, false)
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use std::{collections::BTreeSet, collections::HashMap, fmt, fs};
+
+use crate::get_item_value;
+
+/// Decides which characters are items and what each of them is worth.
+pub trait PriorityScheme {
+    /// The priority of `item`, or `None` if it is not an item in this scheme.
+    fn priority(&self, item: char) -> Option<u32>;
+}
+
+/// The puzzle rules: a-z are 1-26, A-Z are 27-52.
+pub struct Puzzle;
+
+impl PriorityScheme for Puzzle {
+    fn priority(&self, item: char) -> Option<u32> {
+        u8::try_from(item)
+            .ok()
+            .filter(u8::is_ascii_alphabetic)
+            .map(|item| get_item_value(item) as u32)
+    }
+}
+
+/// Digits 0-9 are items with priorities 1-10.
+pub struct Digits;
+
+impl PriorityScheme for Digits {
+    fn priority(&self, item: char) -> Option<u32> {
+        item.to_digit(10).map(|digit| digit + 1)
+    }
+}
+
+/// Priorities read from a file with one `<item> <priority>` pair per line, so any Unicode
+/// character can be an item. Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, PartialEq)]
+pub struct LookupTable {
+    priorities: HashMap<char, u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseTableError {
+    Malformed { line: usize, content: String },
+    Duplicate { line: usize, item: char },
+}
+
+impl fmt::Display for ParseTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseTableError::Malformed { line, content } => write!(
+                f,
+                "line {}: expected \"<item> <priority>\", got {:?}",
+                line, content
+            ),
+            ParseTableError::Duplicate { line, item } => {
+                write!(f, "line {}: {:?} is listed twice", line, item)
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for LookupTable {
+    type Err = ParseTableError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut priorities = HashMap::new();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let malformed = || ParseTableError::Malformed {
+                line: index + 1,
+                content: line.to_string(),
+            };
+            let (item, priority) = line.split_once(char::is_whitespace).ok_or_else(malformed)?;
+            let mut chars = item.chars();
+            let item = match (chars.next(), chars.next()) {
+                (Some(item), None) => item,
+                _ => return Err(malformed()),
+            };
+            let priority: u32 = priority.trim().parse().map_err(|_| malformed())?;
+            if priorities.insert(item, priority).is_some() {
+                return Err(ParseTableError::Duplicate {
+                    line: index + 1,
+                    item,
+                });
+            }
+        }
+        Ok(LookupTable { priorities })
+    }
+}
+
+impl PriorityScheme for LookupTable {
+    fn priority(&self, item: char) -> Option<u32> {
+        self.priorities.get(&item).copied()
+    }
+}
+
+/// Parses the `--priorities` argument: `puzzle`, `digits` or the path of a lookup table file.
+pub fn scheme_from_arg(arg: &str) -> Result<Box<dyn PriorityScheme>, String> {
+    match arg {
+        "puzzle" => Ok(Box::new(Puzzle)),
+        "digits" => Ok(Box::new(Digits)),
+        file_path => {
+            let content = fs::read_to_string(file_path)
+                .map_err(|err| format!("could not read {}: {}", file_path, err))?;
+            content
+                .parse::<LookupTable>()
+                .map(|table| Box::new(table) as Box<dyn PriorityScheme>)
+                .map_err(|err| format!("{}: {}", file_path, err))
+        }
+    }
+}
+
+/// The distinct items among `items`, ordered by priority; characters that are not items are
+/// skipped.
+pub fn item_set(items: &[char], scheme: &dyn PriorityScheme) -> BTreeSet<(u32, char)> {
+    items
+        .iter()
+        .filter_map(|&item| scheme.priority(item).map(|priority| (priority, item)))
+        .collect()
+}
+
+/// The items found in every one of `parts`, ordered by priority.
+pub fn common_items(parts: &[&[char]], scheme: &dyn PriorityScheme) -> BTreeSet<(u32, char)> {
+    parts
+        .iter()
+        .map(|part| item_set(part, scheme))
+        .reduce(|common, items| common.intersection(&items).copied().collect())
+        .unwrap_or_default()
+}
+
+/// Rucksack and badge priority sums under any scheme, splitting compartments by character
+/// count. The input should have been validated; the highest priority wins ambiguous lines.
+pub fn priority_sums(
+    rucksacks: &[&str],
+    group_size: usize,
+    scheme: &dyn PriorityScheme,
+) -> (u32, u32) {
+    let rucksacks: Vec<Vec<char>> = rucksacks.iter().map(|r| r.chars().collect()).collect();
+    let highest = |parts: &[&[char]]| -> u32 {
+        common_items(parts, scheme)
+            .last()
+            .expect("Should have been validated")
+            .0
+    };
+
+    let rucksack_value_sum = rucksacks
+        .iter()
+        .map(|items| {
+            let (a, b) = items.split_at(items.len() / 2);
+            highest(&[a, b])
+        })
+        .sum();
+    let elf_badge_value_sum = rucksacks
+        .chunks(group_size)
+        .map(|group| {
+            highest(
+                &group
+                    .iter()
+                    .map(|items| items.as_slice())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .sum();
+    (rucksack_value_sum, elf_badge_value_sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::priority::{priority_sums, Digits, LookupTable, ParseTableError, Puzzle};
+
+    #[test]
+    fn test_puzzle_scheme_matches_example() {
+        let example = [
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ];
+        assert_eq!((157, 70), priority_sums(&example, 3, &Puzzle));
+    }
+
+    #[test]
+    fn test_digits() {
+        // shared digits 3, 0 and 1; the badge is 1
+        let rucksacks = ["12343567", "91008005", "1716"];
+        assert_eq!((4 + 1 + 2, 2), priority_sums(&rucksacks, 3, &Digits));
+    }
+
+    #[test]
+    fn test_lookup_table() {
+        let table: LookupTable = "# greek\nα 1\nβ 2\n\nΩ 48\n".parse().unwrap();
+        let rucksacks = ["αβΩβ", "ΩαΩα"];
+        assert_eq!((2 + 48, 48), priority_sums(&rucksacks, 2, &table));
+    }
+
+    #[test]
+    fn test_lookup_table_errors() {
+        assert_eq!(
+            Err(ParseTableError::Malformed {
+                line: 2,
+                content: "ab 3".to_string()
+            }),
+            "a 1\nab 3".parse::<LookupTable>()
+        );
+        assert_eq!(
+            Err(ParseTableError::Duplicate { line: 2, item: 'a' }),
+            "a 1\na 2".parse::<LookupTable>()
+        );
+    }
+}
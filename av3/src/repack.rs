@@ -0,0 +1,161 @@
+use std::fmt;
+
+use crate::{find_duplicate, get_item_value, split_into_compartments};
+
+/// One item taken out of its compartment and put into compartment `to` (1 or 2).
+#[derive(Debug, PartialEq)]
+pub struct Move {
+    pub item: u8,
+    pub to: usize,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}->{}", self.item as char, self.to)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Plan {
+    pub moves: Vec<Move>,
+    pub repacked: String,
+}
+
+/// Finds the fewest item moves that leave the two compartments without a shared item while
+/// keeping them the same size, or `None` if no arrangement can do that.
+///
+/// Every item type has to end up in a single compartment. Keeping a type in the first
+/// compartment costs the copies found in the second one and the other way around, so the
+/// cheapest split of the types filling exactly half of the rucksack is found with a knapsack
+/// over the item types. Items that stay keep their positions and moved items take the freed
+/// places in order.
+pub fn plan_repack(rucksack: &[u8]) -> Option<Plan> {
+    let (com_a, com_b) = split_into_compartments(rucksack);
+    let mut counts = [(0, 0); 53];
+    for &c in com_a {
+        counts[get_item_value(c) as usize].0 += 1;
+    }
+    for &c in com_b {
+        counts[get_item_value(c) as usize].1 += 1;
+    }
+    let types: Vec<(u8, usize, usize)> = (1..=52)
+        .filter(|&value| counts[value as usize] != (0, 0))
+        .map(|value| (value, counts[value as usize].0, counts[value as usize].1))
+        .collect();
+
+    // cost[i][size]: fewest moves placing the first i types with `size` items in the first
+    // compartment
+    let half = com_a.len();
+    let mut cost: Vec<Vec<Option<usize>>> = vec![vec![None; half + 1]; types.len() + 1];
+    cost[0][0] = Some(0);
+    for (i, &(_, in_a, in_b)) in types.iter().enumerate() {
+        for size in 0..=half {
+            let Some(current) = cost[i][size] else {
+                continue;
+            };
+            let keep_in_b = current + in_a;
+            if cost[i + 1][size].is_none_or(|best| keep_in_b < best) {
+                cost[i + 1][size] = Some(keep_in_b);
+            }
+            let to_a = size + in_a + in_b;
+            let keep_in_a = current + in_b;
+            if to_a <= half && cost[i + 1][to_a].is_none_or(|best| keep_in_a < best) {
+                cost[i + 1][to_a] = Some(keep_in_a);
+            }
+        }
+    }
+    cost[types.len()][half]?;
+
+    let mut in_first = [false; 53];
+    let mut size = half;
+    for (i, &(value, in_a, in_b)) in types.iter().enumerate().rev() {
+        let previous = size.checked_sub(in_a + in_b).and_then(|s| cost[i][s]);
+        if previous.is_some_and(|c| Some(c + in_b) == cost[i + 1][size]) {
+            in_first[value as usize] = true;
+            size -= in_a + in_b;
+        }
+    }
+
+    let stays = |c: u8, first: bool| in_first[get_item_value(c) as usize] == first;
+    let mut to_a = com_b.iter().copied().filter(|&c| stays(c, true));
+    let mut to_b = com_a.iter().copied().filter(|&c| stays(c, false));
+    let new_a: Vec<u8> = com_a
+        .iter()
+        .map(|&c| {
+            if stays(c, true) {
+                c
+            } else {
+                to_a.next().unwrap()
+            }
+        })
+        .collect();
+    let new_b: Vec<u8> = com_b
+        .iter()
+        .map(|&c| {
+            if stays(c, false) {
+                c
+            } else {
+                to_b.next().unwrap()
+            }
+        })
+        .collect();
+
+    let moves = com_a
+        .iter()
+        .filter(|&&c| stays(c, false))
+        .map(|&item| Move { item, to: 2 })
+        .chain(
+            com_b
+                .iter()
+                .filter(|&&c| stays(c, true))
+                .map(|&item| Move { item, to: 1 }),
+        )
+        .collect();
+    debug_assert_eq!(None, find_duplicate(&new_a, &new_b));
+    Some(Plan {
+        moves,
+        repacked: new_a.into_iter().chain(new_b).map(char::from).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repack::{plan_repack, Move};
+    use crate::{find_duplicate, split_into_compartments};
+
+    #[test]
+    fn test_single_duplicate_swaps_once() {
+        let plan = plan_repack(b"vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+        // one p joins the other and a single item takes its place
+        assert_eq!(2, plan.moves.len());
+        assert!(plan.moves.iter().any(|m| m.item == b'p'));
+
+        let (a, b) = split_into_compartments(plan.repacked.as_bytes());
+        assert_eq!(None, find_duplicate(a, b));
+        assert_eq!(12, a.len());
+    }
+
+    #[test]
+    fn test_already_disjoint() {
+        let plan = plan_repack(b"abcd").unwrap();
+        assert!(plan.moves.is_empty());
+        assert_eq!("abcd", plan.repacked);
+    }
+
+    #[test]
+    fn test_moves_are_minimal() {
+        // a and b are both shared, swapping one a with one b separates them
+        let plan = plan_repack(b"abcdabef").unwrap();
+        assert_eq!(
+            vec![Move { item: b'a', to: 2 }, Move { item: b'b', to: 1 }],
+            plan.moves
+        );
+        assert_eq!("bbcdaaef", plan.repacked);
+    }
+
+    #[test]
+    fn test_impossible() {
+        // three a's cannot fit in a compartment of two
+        assert_eq!(None, plan_repack(b"aaab"));
+    }
+}
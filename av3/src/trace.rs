@@ -0,0 +1,80 @@
+use std::io::{self, Write};
+
+use crate::{find_common_item, find_duplicate, get_item_value, split_into_compartments};
+
+/// Writes every rucksack with its compartments and duplicate, every group with its badge right
+/// after its last rucksack, then the two sums in the same format as the normal output.
+///
+/// The rucksacks should have been validated first.
+pub fn write_trace(
+    rucksacks: &[&[u8]],
+    group_size: usize,
+    output: &mut impl Write,
+) -> io::Result<(u32, u32)> {
+    let mut rucksack_value_sum = 0;
+    let mut elf_badge_value_sum = 0;
+
+    for (index, rucksack) in rucksacks.iter().enumerate() {
+        let (com_a, com_b) = split_into_compartments(rucksack);
+        let duplicate = find_duplicate(com_a, com_b).expect("Should have been validated");
+        let value = get_item_value(duplicate) as u32;
+        rucksack_value_sum += value;
+        writeln!(
+            output,
+            "line {}: {} {} duplicate {} {}",
+            index + 1,
+            String::from_utf8_lossy(com_a),
+            String::from_utf8_lossy(com_b),
+            duplicate as char,
+            value
+        )?;
+
+        if (index + 1).is_multiple_of(group_size) {
+            let first = index + 1 - group_size;
+            let badge =
+                find_common_item(&rucksacks[first..=index]).expect("Should have been validated");
+            let value = get_item_value(badge) as u32;
+            elf_badge_value_sum += value;
+            writeln!(
+                output,
+                "group {} (lines {}-{}): badge {} {}",
+                first / group_size + 1,
+                first + 1,
+                index + 1,
+                badge as char,
+                value
+            )?;
+        }
+    }
+
+    writeln!(output, "{:?} rucksack value sum", rucksack_value_sum)?;
+    writeln!(output, "{:?} elf badge sum", elf_badge_value_sum)?;
+    Ok((rucksack_value_sum, elf_badge_value_sum))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trace::write_trace;
+
+    #[test]
+    fn test_trace() {
+        let rucksacks: [&[u8]; 3] = [
+            b"vJrwpWtwJgWrhcsFMMfFFhFp",
+            b"jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            b"PmmdzqPrVvPwwTWBwg",
+        ];
+        let mut output = vec![];
+        let sums = write_trace(&rucksacks, 3, &mut output).unwrap();
+
+        assert_eq!((16 + 38 + 42, 18), sums);
+        assert_eq!(
+            "line 1: vJrwpWtwJgWr hcsFMMfFFhFp duplicate p 16\n\
+             line 2: jqHRNqRjqzjGDLGL rsFMfFZSrLrFZsSL duplicate L 38\n\
+             line 3: PmmdzqPrV vPwwTWBwg duplicate P 42\n\
+             group 1 (lines 1-3): badge r 18\n\
+             96 rucksack value sum\n\
+             18 elf badge sum\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
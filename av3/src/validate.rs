@@ -0,0 +1,307 @@
+use std::{collections::BTreeSet, fmt};
+
+use crate::item_set::ItemSet;
+use crate::priority::{item_set, PriorityScheme};
+
+#[derive(Debug, PartialEq)]
+pub enum Problem {
+    OddLength(usize),
+    InvalidItems(Vec<char>),
+    NoSharedItem,
+    MultipleSharedItems(Vec<char>),
+    IncompleteGroup { len: usize, size: usize },
+    NoBadge,
+    MultipleBadges(Vec<char>),
+}
+
+impl Problem {
+    /// Whether the rucksack itself cannot be read as two compartments of items.
+    pub fn is_malformed(&self) -> bool {
+        matches!(self, Problem::OddLength(_) | Problem::InvalidItems(_))
+    }
+
+    /// Whether several items qualify, which `--allow-ambiguous` settles by highest priority.
+    pub fn is_ambiguous(&self) -> bool {
+        matches!(
+            self,
+            Problem::MultipleSharedItems(_) | Problem::MultipleBadges(_)
+        )
+    }
+
+    /// Whether the puzzle answer cannot be computed with this problem present. Several shared
+    /// items or badges only stop it when ambiguity is not allowed.
+    pub fn is_fatal(&self, allow_ambiguous: bool) -> bool {
+        !(allow_ambiguous && self.is_ambiguous())
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::OddLength(len) => write!(f, "odd number of items ({})", len),
+            Problem::InvalidItems(items) => write!(f, "invalid items {:?}", items),
+            Problem::NoSharedItem => write!(f, "the compartments share no item"),
+            Problem::MultipleSharedItems(items) => {
+                write!(f, "the compartments share several items {:?}", items)
+            }
+            Problem::IncompleteGroup { len, size } => {
+                write!(
+                    f,
+                    "incomplete group of {} instead of {} rucksacks",
+                    len, size
+                )
+            }
+            Problem::NoBadge => write!(f, "the group has no common badge"),
+            Problem::MultipleBadges(items) => {
+                write!(f, "the group has several common items {:?}", items)
+            }
+        }
+    }
+}
+
+/// A problem together with the 1-based line it was found on; group problems use the first line
+/// of the group.
+#[derive(Debug, PartialEq)]
+pub struct Issue {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+/// The item sets the checks compare: `ItemSet` under the puzzle rules, `(priority, item)` pairs
+/// under any other scheme.
+trait Items: Clone {
+    fn intersection(&self, other: &Self) -> Self;
+    fn len(&self) -> usize;
+    /// The items in order of increasing priority.
+    fn chars(&self) -> Vec<char>;
+}
+
+impl Items for ItemSet {
+    fn intersection(&self, other: &Self) -> Self {
+        ItemSet::intersection(self, other)
+    }
+    fn len(&self) -> usize {
+        ItemSet::len(self)
+    }
+    fn chars(&self) -> Vec<char> {
+        self.iter().map(char::from).collect()
+    }
+}
+
+impl Items for BTreeSet<(u32, char)> {
+    fn intersection(&self, other: &Self) -> Self {
+        BTreeSet::intersection(self, other).copied().collect()
+    }
+    fn len(&self) -> usize {
+        BTreeSet::len(self)
+    }
+    fn chars(&self) -> Vec<char> {
+        self.iter().map(|(_, item)| *item).collect()
+    }
+}
+
+/// What the checks need to know about one rucksack, whatever its encoding.
+struct Contents<S> {
+    len: usize,
+    invalid: Vec<char>,
+    /// Both compartments, unless the items cannot be split evenly.
+    compartments: Option<(S, S)>,
+    items: S,
+}
+
+fn read_chars(rucksack: &str, scheme: &dyn PriorityScheme) -> Contents<BTreeSet<(u32, char)>> {
+    let items: Vec<char> = rucksack.chars().collect();
+    let (a, b) = items.split_at(items.len() / 2);
+    Contents {
+        len: items.len(),
+        invalid: items
+            .iter()
+            .copied()
+            .filter(|c| scheme.priority(*c).is_none())
+            .collect(),
+        compartments: items
+            .len()
+            .is_multiple_of(2)
+            .then(|| (item_set(a, scheme), item_set(b, scheme))),
+        items: item_set(&items, scheme),
+    }
+}
+
+/// Reads an ASCII rucksack under the puzzle rules, where every byte that is not a letter is
+/// invalid.
+fn read_ascii(rucksack: &[u8]) -> Contents<ItemSet> {
+    let letters = |part: &[u8]| -> ItemSet {
+        part.iter()
+            .copied()
+            .filter(u8::is_ascii_alphabetic)
+            .collect()
+    };
+    let (a, b) = rucksack.split_at(rucksack.len() / 2);
+    let (a, b) = (letters(a), letters(b));
+    Contents {
+        len: rucksack.len(),
+        invalid: rucksack
+            .iter()
+            .filter(|c| !c.is_ascii_alphabetic())
+            .map(|&c| char::from(c))
+            .collect(),
+        compartments: rucksack.len().is_multiple_of(2).then_some((a, b)),
+        items: a.union(&b),
+    }
+}
+
+fn check_rucksack<S: Items>(rucksack: &Contents<S>) -> Vec<Problem> {
+    let mut problems = vec![];
+    if !rucksack.invalid.is_empty() {
+        problems.push(Problem::InvalidItems(rucksack.invalid.clone()));
+    }
+    let Some((a, b)) = &rucksack.compartments else {
+        problems.push(Problem::OddLength(rucksack.len));
+        return problems;
+    };
+
+    let shared = a.intersection(b);
+    match shared.len() {
+        0 => problems.push(Problem::NoSharedItem),
+        1 => {}
+        _ => problems.push(Problem::MultipleSharedItems(shared.chars())),
+    }
+    problems
+}
+
+fn check_group<S: Items>(group: &[Contents<S>], group_size: usize) -> Option<Problem> {
+    if group.len() < group_size {
+        return Some(Problem::IncompleteGroup {
+            len: group.len(),
+            size: group_size,
+        });
+    }
+    let (first, rest) = group.split_first()?;
+    let badges = rest.iter().fold(first.items.clone(), |common, rucksack| {
+        common.intersection(&rucksack.items)
+    });
+    match badges.len() {
+        0 => Some(Problem::NoBadge),
+        1 => None,
+        _ => Some(Problem::MultipleBadges(badges.chars())),
+    }
+}
+
+/// Checks every rucksack and every group of `group_size` rucksacks, collecting all problems
+/// instead of stopping at the first. Characters without a priority in `scheme` are invalid.
+pub fn validate(rucksacks: &[&str], group_size: usize, scheme: &dyn PriorityScheme) -> Vec<Issue> {
+    collect_issues(rucksacks, group_size, |rucksack| {
+        read_chars(rucksack, scheme)
+    })
+}
+
+/// The same checks as `validate` under the puzzle rules, on lines already known to be ASCII,
+/// comparing `ItemSet`s instead of sets of characters.
+pub fn validate_ascii(rucksacks: &[&[u8]], group_size: usize) -> Vec<Issue> {
+    collect_issues(rucksacks, group_size, |rucksack| read_ascii(rucksack))
+}
+
+/// Reads every rucksack once and checks it and its group, issues sorted by line.
+fn collect_issues<R, S: Items>(
+    rucksacks: &[R],
+    group_size: usize,
+    read: impl Fn(&R) -> Contents<S>,
+) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = vec![];
+    for (index, group) in rucksacks.chunks(group_size).enumerate() {
+        let first_line = index * group_size + 1;
+        let group: Vec<Contents<S>> = group.iter().map(&read).collect();
+        for (offset, rucksack) in group.iter().enumerate() {
+            issues.extend(check_rucksack(rucksack).into_iter().map(|problem| Issue {
+                line: first_line + offset,
+                problem,
+            }));
+        }
+        if let Some(problem) = check_group(&group, group_size) {
+            issues.push(Issue {
+                line: first_line,
+                problem,
+            });
+        }
+    }
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+pub fn print_issues(issues: &[Issue]) {
+    for issue in issues {
+        println!("line {}: {}", issue.line, issue.problem);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::priority::Puzzle;
+    use crate::validate::{validate, validate_ascii, Issue, Problem};
+
+    #[test]
+    fn test_valid_example() {
+        let example = [
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+        ];
+        assert_eq!(Vec::<Issue>::new(), validate(&example, 3, &Puzzle));
+    }
+
+    #[test]
+    fn test_reports_every_problem() {
+        let rucksacks = ["abcxa", "ab1cb2", "abcd", "abab", "aéaé"];
+        let issues = validate(&rucksacks, 2, &Puzzle);
+        let found: Vec<(usize, &Problem)> = issues
+            .iter()
+            .map(|issue| (issue.line, &issue.problem))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (1, &Problem::OddLength(5)),
+                (1, &Problem::MultipleBadges(vec!['a', 'b', 'c'])),
+                (2, &Problem::InvalidItems(vec!['1', '2'])),
+                (3, &Problem::NoSharedItem),
+                (3, &Problem::MultipleBadges(vec!['a', 'b'])),
+                (4, &Problem::MultipleSharedItems(vec!['a', 'b'])),
+                (5, &Problem::InvalidItems(vec!['é', 'é'])),
+                (5, &Problem::IncompleteGroup { len: 1, size: 2 }),
+            ],
+            found
+        );
+    }
+
+    #[test]
+    fn test_group_without_badge() {
+        let issues = validate(&["abcb", "defe"], 2, &Puzzle);
+        assert_eq!(
+            vec![Issue {
+                line: 1,
+                problem: Problem::NoBadge
+            }],
+            issues
+        );
+    }
+
+    #[test]
+    fn test_ascii_checks_agree() {
+        let rucksacks = [
+            "abcxa",
+            "ab1cb2",
+            "abcd",
+            "abab",
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+        ];
+        let bytes: Vec<&[u8]> = rucksacks.iter().map(|r| r.as_bytes()).collect();
+        for group_size in [1, 2, 3] {
+            assert_eq!(
+                validate(&rucksacks, group_size, &Puzzle),
+                validate_ascii(&bytes, group_size)
+            );
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::ops::RangeInclusive;
+
+use crate::interval_set::IntervalSet;
+
+/// A run of consecutive sections assigned to the same number of elves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub sections: RangeInclusive<i32>,
+    pub elves: u32,
+}
+
+/// Counts the elves on every section with a sweep line: each assignment adds one elf at its
+/// first section and removes it right after its last one, and the sorted events are walked once.
+/// Sections without elves are left out.
+pub fn sweep_coverage<'a>(
+    ranges: impl IntoIterator<Item = &'a RangeInclusive<i32>>,
+) -> Vec<Segment> {
+    let mut events: Vec<(i64, i64)> = ranges
+        .into_iter()
+        .filter(|range| !range.is_empty())
+        .flat_map(|range| [(*range.start() as i64, 1), (*range.end() as i64 + 1, -1)])
+        .collect();
+    events.sort();
+
+    let mut segments = vec![];
+    let mut elves = 0;
+    let mut index = 0;
+    while index < events.len() {
+        let position = events[index].0;
+        while index < events.len() && events[index].0 == position {
+            elves += events[index].1;
+            index += 1;
+        }
+        // the last event always brings the count back to zero
+        if elves == 0 {
+            continue;
+        }
+        let end = (events[index].0 - 1) as i32;
+        match segments.last_mut() {
+            // an elf leaving as another one arrives does not change the count
+            Some(Segment {
+                sections,
+                elves: last,
+            }) if *last == elves as u32 && *sections.end() as i64 + 1 == position => {
+                *sections = *sections.start()..=end;
+            }
+            _ => segments.push(Segment {
+                sections: position as i32..=end,
+                elves: elves as u32,
+            }),
+        }
+    }
+    segments
+}
+
+/// The highest number of elves on a section and the sections where it is reached.
+pub fn max_coverage(segments: &[Segment]) -> (u32, Vec<RangeInclusive<i32>>) {
+    let max = segments
+        .iter()
+        .map(|segment| segment.elves)
+        .max()
+        .unwrap_or(0);
+    let sections = segments
+        .iter()
+        .filter(|segment| segment.elves == max)
+        .map(|segment| segment.sections.clone())
+        .collect();
+    (max, sections)
+}
+
+/// The sections assigned to at least `elves` elves.
+pub fn at_least(segments: &[Segment], elves: u32) -> IntervalSet<i32> {
+    segments
+        .iter()
+        .filter(|segment| segment.elves >= elves)
+        .map(|segment| segment.sections.clone())
+        .collect()
+}
+
+const LEVELS: &[u8] = b" .:-=+*#%@";
+
+/// One character per section of `within`, from ' ' for no elf to '@' for the most crowded ones.
+pub fn heatmap(segments: &[Segment], within: &RangeInclusive<i32>) -> String {
+    let max = max_coverage(segments).0.max(1);
+    let top = (LEVELS.len() - 1) as u32;
+    within
+        .clone()
+        .map(|section| {
+            let elves = segments
+                .iter()
+                .find(|segment| segment.sections.contains(&section))
+                .map_or(0, |segment| segment.elves);
+            LEVELS[(elves * top).div_ceil(max) as usize] as char
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coverage::{at_least, heatmap, max_coverage, sweep_coverage, Segment};
+    use crate::parse_pair;
+
+    fn example() -> Vec<Segment> {
+        let ranges: Vec<_> = [
+            "2-4,6-8", "2-3,4-5", "5-7,7-9", "2-8,3-7", "6-6,4-6", "2-6,4-8",
+        ]
+        .into_iter()
+        .map(parse_pair)
+        .collect();
+        sweep_coverage(
+            ranges
+                .iter()
+                .flat_map(|(range_a, range_b)| [range_a, range_b]),
+        )
+    }
+
+    #[test]
+    fn test_sweep() {
+        let counts: Vec<(i32, i32, u32)> = example()
+            .iter()
+            .map(|segment| {
+                (
+                    *segment.sections.start(),
+                    *segment.sections.end(),
+                    segment.elves,
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (2, 2, 4),
+                (3, 3, 5),
+                (4, 5, 7),
+                (6, 6, 8),
+                (7, 7, 6),
+                (8, 8, 4),
+                (9, 9, 1)
+            ],
+            counts
+        );
+    }
+
+    #[test]
+    fn test_gap_between_assignments() {
+        let ranges = [1..=2, 5..=5, 2..=2];
+        assert_eq!(
+            vec![
+                Segment {
+                    sections: 1..=1,
+                    elves: 1
+                },
+                Segment {
+                    sections: 2..=2,
+                    elves: 2
+                },
+                Segment {
+                    sections: 5..=5,
+                    elves: 1
+                },
+            ],
+            sweep_coverage(&ranges)
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let segments = example();
+        assert_eq!((8, vec![6..=6]), max_coverage(&segments));
+        assert_eq!(&[4..=6], at_least(&segments, 7).ranges());
+        assert_eq!(&[2..=8], at_least(&segments, 4).ranges());
+        assert_eq!(" +*%%@#+: ", heatmap(&segments, &(1..=10)));
+    }
+}
@@ -0,0 +1,114 @@
+use std::ops::RangeInclusive;
+
+use crate::{Section, SectionArithmetic};
+
+/// Section ranges kept sorted and merged, so overlapping or touching assignments become one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T: Section> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+
+    /// Adds a range, merging it with every range it overlaps or touches. Empty ranges are
+    /// ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let mut merged = range;
+        let mut kept = Vec::with_capacity(self.ranges.len() + 1);
+        for existing in self.ranges.drain(..) {
+            match merged.union(&existing) {
+                Some(union) => merged = union,
+                None => kept.push(existing),
+            }
+        }
+        let index = kept.partition_point(|range| range.start() < merged.start());
+        kept.insert(index, merged);
+        self.ranges = kept;
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    /// The number of sections in at least one range.
+    pub fn covered(&self) -> u64 {
+        self.ranges.iter().map(|range| range.len()).sum()
+    }
+
+    /// The sections between consecutive ranges.
+    pub fn gaps(&self) -> Vec<RangeInclusive<T>> {
+        self.ranges
+            .windows(2)
+            .map(|pair| pair[0].end().next().unwrap()..=pair[1].start().previous().unwrap())
+            .collect()
+    }
+
+    /// The sections of `within` that no range covers; none if `within` is empty.
+    pub fn unassigned(&self, within: &RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        if within.is_empty() {
+            return vec![];
+        }
+        self.ranges
+            .iter()
+            .fold(vec![within.clone()], |remaining, range| {
+                remaining
+                    .iter()
+                    .flat_map(|rest| rest.difference(range))
+                    .collect()
+            })
+    }
+}
+
+impl<T: Section> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval_set::IntervalSet;
+    use crate::parse_range;
+
+    #[test]
+    fn test_merges_and_sorts() {
+        let set: IntervalSet<i32> = ["6-8", "2-4", "3-5", "10-12", "13-13", "20-19"]
+            .into_iter()
+            .map(parse_range)
+            .collect();
+        // 2-4 and 3-5 overlap, 6-8 touches them, 13 touches 10-12 and 20-19 is empty
+        assert_eq!(&[2..=8, 10..=13], set.ranges());
+        assert_eq!(11, set.covered());
+        assert_eq!(vec![9..=9], set.gaps());
+    }
+
+    #[test]
+    fn test_insert_bridges_ranges() {
+        let mut set: IntervalSet<i32> = [1..=2, 5..=6, 9..=10].into_iter().collect();
+        set.insert(3..=8);
+        assert_eq!(&[1..=10], set.ranges());
+        assert!(set.gaps().is_empty());
+    }
+
+    #[test]
+    fn test_unassigned() {
+        let set: IntervalSet<i32> = [2..=4, 7..=7, 9..=20].into_iter().collect();
+        assert_eq!(vec![1..=1, 5..=6, 8..=8], set.unassigned(&(1..=10)));
+        assert!(set.unassigned(&(9..=12)).is_empty());
+        assert_eq!(vec![1..=10], IntervalSet::new().unassigned(&(1..=10)));
+        // a camp of zero sections has nothing left to assign
+        let empty = parse_range("1-0");
+        assert!(IntervalSet::<i32>::new().unassigned(&empty).is_empty());
+        assert!(set.unassigned(&empty).is_empty());
+    }
+}
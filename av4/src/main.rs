@@ -1,43 +1,250 @@
-use std::{fs, ops::Range};
+mod coverage;
+mod interval_set;
+mod sweep;
+
+use std::{env, fs, ops::RangeInclusive, str::FromStr};
+
+use interval_set::IntervalSet;
 
 trait RangeContainsRange<Range> {
     fn fully_contains(&self, other: &Range) -> bool;
     fn overlaps(&self, other: &Range) -> bool;
+    /// The sections found in both ranges.
+    fn intersection(&self, other: &Range) -> Option<Range>;
+}
+
+impl<T: Ord + Copy> RangeContainsRange<RangeInclusive<T>> for RangeInclusive<T> {
+    fn fully_contains(&self, other: &RangeInclusive<T>) -> bool {
+        self.start() <= other.start() && self.end() >= other.end()
+    }
+    fn overlaps(&self, other: &RangeInclusive<T>) -> bool {
+        self.start() <= other.end() && other.start() <= self.end()
+    }
+    fn intersection(&self, other: &RangeInclusive<T>) -> Option<RangeInclusive<T>> {
+        // a reversed range like 8..=3 holds no sections to share
+        (!self.is_empty() && !other.is_empty() && self.overlaps(other))
+            .then(|| *self.start().max(other.start())..=*self.end().min(other.end()))
+    }
+}
+
+/// A section number with neighbours, so that touching ranges can be joined and sections counted.
+trait Section: Ord + Copy {
+    fn next(self) -> Option<Self>;
+    fn previous(self) -> Option<Self>;
+    /// The number of sections from `self` to `end`, both included, saturating at `u64::MAX` for
+    /// the one range too long to count, the whole of `i64`.
+    fn count_to(self, end: Self) -> u64;
+}
+
+macro_rules! impl_section {
+    ($($t:ty),*) => {$(
+        impl Section for $t {
+            fn next(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+            fn previous(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+            fn count_to(self, end: Self) -> u64 {
+                u64::try_from((end as i128 - self as i128 + 1).max(0)).unwrap_or(u64::MAX)
+            }
+        }
+    )*};
+}
+
+impl_section!(i32, i64, u32);
+
+trait SectionArithmetic<Range> {
+    /// Both ranges as one, if they overlap or touch.
+    fn union(&self, other: &Range) -> Option<Range>;
+    /// The sections of `self` outside `other`: none, one range, or two when `other` sits inside.
+    fn difference(&self, other: &Range) -> Vec<Range>;
+    /// The number of sections in the range.
+    fn len(&self) -> u64;
+}
+
+impl<T: Section> SectionArithmetic<RangeInclusive<T>> for RangeInclusive<T> {
+    fn union(&self, other: &RangeInclusive<T>) -> Option<RangeInclusive<T>> {
+        if other.is_empty() {
+            return Some(self.clone());
+        }
+        if self.is_empty() {
+            return Some(other.clone());
+        }
+        let (first, second) = if self.start() <= other.start() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let touching = first
+            .end()
+            .next()
+            .is_none_or(|next| *second.start() <= next);
+        touching.then(|| *first.start()..=*first.end().max(second.end()))
+    }
+    fn difference(&self, other: &RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        if self.is_empty() {
+            return vec![];
+        }
+        if other.is_empty() || !self.overlaps(other) {
+            return vec![self.clone()];
+        }
+        let before = other
+            .start()
+            .previous()
+            .filter(|end| self.start() <= end)
+            .map(|end| *self.start()..=end);
+        let after = other
+            .end()
+            .next()
+            .filter(|start| start <= self.end())
+            .map(|start| start..=*self.end());
+        before.into_iter().chain(after).collect()
+    }
+    fn len(&self) -> u64 {
+        self.start().count_to(*self.end())
+    }
+}
+
+/// Parses a section assignment like "2-4", which includes both sections 2 and 4.
+fn parse_range(str_range: &str) -> RangeInclusive<i32> {
+    let range_vec: Vec<_> = str_range
+        .split("-")
+        .map(|x| x.parse::<i32>().unwrap())
+        .collect();
+    range_vec[0]..=range_vec[1]
+}
+
+fn parse_pair(line: &str) -> (RangeInclusive<i32>, RangeInclusive<i32>) {
+    let (range_a, range_b) = line.split_once(",").unwrap();
+    (parse_range(range_a), parse_range(range_b))
+}
+
+/// Returns the value following `--name` on the command line, if present.
+fn get_option<T: FromStr>(args: &[String], name: &str) -> Option<T> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("Invalid value {:?} for {}", value, name))
+        })
+}
+
+/// Checks that the assignments cover every section of the camp, `1..=sections`.
+fn run_coverage(ranges: &[(RangeInclusive<i32>, RangeInclusive<i32>)], sections: i32) {
+    let assigned: IntervalSet<i32> = ranges
+        .iter()
+        .flat_map(|(range_a, range_b)| [range_a.clone(), range_b.clone()])
+        .collect();
+    println!(
+        "{} sections assigned in {:?}",
+        assigned.covered(),
+        assigned.ranges()
+    );
+    println!("gaps between assignments: {:?}", assigned.gaps());
+    let unassigned = assigned.unassigned(&(1..=sections));
+    if unassigned.is_empty() {
+        println!("every section in 1..={} is assigned", sections);
+    } else {
+        println!("unassigned in 1..={}: {:?}", sections, unassigned);
+    }
 }
 
-impl RangeContainsRange<Range<i32>> for Range<i32> {
-    fn fully_contains(&self, other: &Range<i32>) -> bool {
-        return self.start <= other.start && self.end >= other.end;
+/// Prints what the two assignments on each line share and what only one of them covers.
+fn run_shared(ranges: &[(RangeInclusive<i32>, RangeInclusive<i32>)]) {
+    let mut shared_sections = 0;
+    for (index, (range_a, range_b)) in ranges.iter().enumerate() {
+        let shared = range_a.intersection(range_b);
+        let shared_len = shared.as_ref().map_or(0, |shared| shared.len());
+        shared_sections += shared_len;
+        println!(
+            "line {}: {:?} and {:?} share {} sections {:?}, together {}, only first {:?}, only second {:?}",
+            index + 1,
+            range_a,
+            range_b,
+            shared_len,
+            shared,
+            range_a
+                .union(range_b)
+                .map_or("not contiguous".to_string(), |union| format!("{:?}", union)),
+            range_a.difference(range_b),
+            range_b.difference(range_a)
+        );
     }
-    fn overlaps(&self, other: &Range<i32>) -> bool {
-        return (self.start <= other.start && self.end >= other.start)
-            || (self.end >= other.end && self.start <= other.end)
-            || self.fully_contains(other)
-            || other.fully_contains(self);
+    println!("{} sections shared in total", shared_sections);
+}
+
+/// Lists every pair of elves, on any lines, whose assignments overlap.
+fn run_conflicts(ranges: &[(RangeInclusive<i32>, RangeInclusive<i32>)]) {
+    let elves = sweep::elves(ranges);
+    let pairs = sweep::overlapping_pairs(&elves);
+    let assignment = |elf: sweep::Elf| &elves[(elf.line - 1) * 2 + elf.index - 1].1;
+    for (elf, other) in &pairs {
+        println!(
+            "{} {:?} overlaps {} {:?}",
+            elf,
+            assignment(*elf),
+            other,
+            assignment(*other)
+        );
+    }
+    println!("{} overlapping pairs of elves", pairs.len());
+}
+
+/// Prints how many elves are assigned to each section of `1..=sections`.
+fn run_heatmap(
+    ranges: &[(RangeInclusive<i32>, RangeInclusive<i32>)],
+    sections: i32,
+    min_elves: u32,
+) {
+    let segments = coverage::sweep_coverage(
+        ranges
+            .iter()
+            .flat_map(|(range_a, range_b)| [range_a, range_b]),
+    );
+    let (max, crowded) = coverage::max_coverage(&segments);
+    println!("at most {} elves, on sections {:?}", max, crowded);
+    let busy = coverage::at_least(&segments, min_elves);
+    println!(
+        "{} sections with at least {} elves: {:?}",
+        busy.covered(),
+        min_elves,
+        busy.ranges()
+    );
+
+    println!("heatmap, ' ' for no elf up to '@' for {} elves:", max);
+    let strip = coverage::heatmap(&segments, &(1..=sections));
+    for (row, chunk) in strip.as_bytes().chunks(100).enumerate() {
+        println!("{:>5} |{}|", row * 100 + 1, String::from_utf8_lossy(chunk));
     }
 }
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
     let file_path = "./ranges.txt";
     let file_content =
         fs::read_to_string(file_path).expect("Should have been able to read the file");
 
-    let ranges = file_content
-        .lines()
-        .map(|x| {
-            let ranges_vec = x
-                .split(",")
-                .map(|str_range| {
-                    let range_vec: Vec<_> = str_range
-                        .split("-")
-                        .map(|x| x.parse::<i32>().unwrap())
-                        .collect();
-                    return range_vec[0]..range_vec[1];
-                })
-                .collect::<Vec<_>>();
-            return (ranges_vec[0].clone(), ranges_vec[1].clone());
-        })
-        .collect::<Vec<_>>();
+    let ranges = file_content.lines().map(parse_pair).collect::<Vec<_>>();
+    let sections = get_option(&args, "--sections").unwrap_or_else(|| {
+        ranges
+            .iter()
+            .map(|(range_a, range_b)| *range_a.end().max(range_b.end()))
+            .max()
+            .unwrap_or(0)
+    });
+    match args.first().map(|arg| arg.as_str()) {
+        Some("conflicts") => return run_conflicts(&ranges),
+        Some("shared") => return run_shared(&ranges),
+        Some("coverage") => return run_coverage(&ranges, sections),
+        Some("heatmap") => {
+            let min_elves = get_option(&args, "--min-elves").unwrap_or(2);
+            return run_heatmap(&ranges, sections, min_elves);
+        }
+        _ => {}
+    }
 
     let num_ranges_consumed: i32 = ranges
         .iter()
@@ -51,7 +258,111 @@ fn main() {
         .map(|(range_a, range_b)| range_a.overlaps(range_b) as i32)
         .sum();
 
-    println!("{:?}", ranges);
     println!("{}", num_ranges_consumed);
     println!("{}", num_ranges_overlap);
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_pair, parse_range, RangeContainsRange, SectionArithmetic};
+
+    #[test]
+    fn test_parse_includes_end() {
+        assert_eq!(2..=4, parse_range("2-4"));
+        assert_eq!((6..=6, 4..=6), parse_pair("6-6,4-6"));
+    }
+
+    #[test]
+    fn test_single_section() {
+        let (single, range) = parse_pair("6-6,4-6");
+        assert!(range.fully_contains(&single));
+        assert!(!single.fully_contains(&range));
+        assert!(single.overlaps(&range));
+        assert!(single.fully_contains(&single));
+        assert!(!(3..=3).overlaps(&(4..=4)));
+    }
+
+    #[test]
+    fn test_adjacent_assignments() {
+        // touching ends share a section, neighbours do not
+        let (range_a, range_b) = parse_pair("5-7,7-9");
+        assert!(range_a.overlaps(&range_b));
+        assert!(range_b.overlaps(&range_a));
+        let (range_a, range_b) = parse_pair("2-3,4-5");
+        assert!(!range_a.overlaps(&range_b));
+        assert!(!range_b.overlaps(&range_a));
+    }
+
+    #[test]
+    fn test_example() {
+        let example = [
+            "2-4,6-8", "2-3,4-5", "5-7,7-9", "2-8,3-7", "6-6,4-6", "2-6,4-8",
+        ];
+        let pairs: Vec<_> = example.iter().map(|line| parse_pair(line)).collect();
+        let consumed = pairs
+            .iter()
+            .filter(|(a, b)| a.fully_contains(b) || b.fully_contains(a))
+            .count();
+        let overlapping = pairs.iter().filter(|(a, b)| a.overlaps(b)).count();
+        assert_eq!((2, 4), (consumed, overlapping));
+    }
+
+    #[test]
+    fn test_other_types() {
+        assert!(('a'..='f').fully_contains(&('b'..='c')));
+        assert!(!(10u64..=20).overlaps(&(21..=30)));
+    }
+
+    #[test]
+    fn test_len_saturates() {
+        assert_eq!(u64::MAX, (i64::MIN..=i64::MAX).len());
+        assert_eq!(u64::MAX, (i64::MIN + 1..=i64::MAX).len());
+        assert_eq!(1 << 32, (i32::MIN..=i32::MAX).len());
+    }
+
+    #[test]
+    fn test_intersection_and_len() {
+        assert_eq!(Some(3..=4), (2..=4).intersection(&(3..=7)));
+        assert_eq!(Some(7..=7), (5..=7).intersection(&(7..=9)));
+        assert_eq!(None, (2..=3).intersection(&(4..=5)));
+        assert_eq!(Some('c'..='d'), ('a'..='d').intersection(&('c'..='z')));
+        assert_eq!(3, parse_range("2-4").len());
+        assert_eq!(1, parse_range("6-6").len());
+    }
+
+    #[test]
+    fn test_union() {
+        assert_eq!(Some(2..=7), (5..=7).union(&(2..=5)));
+        // adjacent sections join, a gap does not
+        assert_eq!(Some(2..=5), (2..=3).union(&(4..=5)));
+        assert_eq!(None, (2..=3).union(&(5..=6)));
+        assert_eq!(Some(1..=i32::MAX), (1..=i32::MAX).union(&(5..=i32::MAX)));
+    }
+
+    #[test]
+    fn test_difference() {
+        assert_eq!(vec![2..=2, 8..=8], (2..=8).difference(&(3..=7)));
+        assert_eq!(vec![5..=6], (5..=7).difference(&(7..=9)));
+        assert_eq!(
+            Vec::<std::ops::RangeInclusive<i32>>::new(),
+            (3..=7).difference(&(2..=8))
+        );
+        assert_eq!(vec![2..=3], (2..=3).difference(&(4..=5)));
+        assert_eq!(vec![1..=1], (0u32..=1).difference(&(0..=0)));
+    }
+
+    #[test]
+    fn test_reversed_range() {
+        let (range, reversed) = parse_pair("1-10,8-3");
+        assert_eq!(None, range.intersection(&reversed));
+        assert_eq!(None, reversed.intersection(&range));
+        assert_eq!(vec![1..=10], range.difference(&reversed));
+        assert_eq!(
+            Vec::<std::ops::RangeInclusive<i32>>::new(),
+            reversed.difference(&range)
+        );
+        assert_eq!(Some(1..=10), range.union(&reversed));
+        assert_eq!(Some(9..=10), reversed.union(&(9..=10)));
+        assert_eq!(0, reversed.len());
+    }
+}
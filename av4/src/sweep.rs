@@ -0,0 +1,122 @@
+use std::{fmt, ops::RangeInclusive};
+
+use crate::RangeContainsRange;
+
+/// The `index`th elf (1 or 2) of the pair on `line` (1-based).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Elf {
+    pub line: usize,
+    pub index: usize,
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} elf {}", self.line, self.index)
+    }
+}
+
+/// Every elf of every line with its assignment.
+pub fn elves<T: Clone>(
+    ranges: &[(RangeInclusive<T>, RangeInclusive<T>)],
+) -> Vec<(Elf, RangeInclusive<T>)> {
+    ranges
+        .iter()
+        .enumerate()
+        .flat_map(|(index, (range_a, range_b))| {
+            [
+                (
+                    Elf {
+                        line: index + 1,
+                        index: 1,
+                    },
+                    range_a.clone(),
+                ),
+                (
+                    Elf {
+                        line: index + 1,
+                        index: 2,
+                    },
+                    range_b.clone(),
+                ),
+            ]
+        })
+        .collect()
+}
+
+/// Finds every pair of elves whose assignments overlap, sorted, each pair with the smaller elf
+/// first.
+///
+/// Assignments are swept in order of their first section while keeping the ones still running.
+/// Each new assignment drops the running ones that ended before it starts and overlaps all the
+/// others, so the work is O(n log n) plus the number of pairs found.
+pub fn overlapping_pairs<T: Ord + Copy>(elves: &[(Elf, RangeInclusive<T>)]) -> Vec<(Elf, Elf)> {
+    let mut order: Vec<&(Elf, RangeInclusive<T>)> = elves
+        .iter()
+        .filter(|(_, range)| !range.is_empty())
+        .collect();
+    order.sort_by_key(|(elf, range)| (*range.start(), *elf));
+
+    let mut running: Vec<&(Elf, RangeInclusive<T>)> = vec![];
+    let mut pairs = vec![];
+    for current in order {
+        let (elf, range) = current;
+        running.retain(|(_, other)| other.end() >= range.start());
+        for (other_elf, other) in &running {
+            debug_assert!(range.overlaps(other));
+            pairs.push((*elf.min(other_elf), *elf.max(other_elf)));
+        }
+        running.push(current);
+    }
+    pairs.sort();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweep::{elves, overlapping_pairs, Elf};
+    use crate::{parse_pair, RangeContainsRange};
+
+    #[test]
+    fn test_example() {
+        let ranges: Vec<_> = ["2-4,6-8", "2-3,4-5", "5-7,7-9"]
+            .into_iter()
+            .map(parse_pair)
+            .collect();
+        let elf = |line, index| Elf { line, index };
+        assert_eq!(
+            vec![
+                (elf(1, 1), elf(2, 1)),
+                (elf(1, 1), elf(2, 2)),
+                (elf(1, 2), elf(3, 1)),
+                (elf(1, 2), elf(3, 2)),
+                (elf(2, 2), elf(3, 1)),
+                (elf(3, 1), elf(3, 2)),
+            ],
+            overlapping_pairs(&elves(&ranges))
+        );
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let ranges: Vec<_> = [
+            "2-4,6-8", "2-3,4-5", "5-7,7-9", "2-8,3-7", "6-6,4-6", "2-6,4-8", "1-1,9-9",
+            "4-4,8-90", "3-5,5-3",
+        ]
+        .into_iter()
+        .map(parse_pair)
+        .collect();
+        let elves = elves(&ranges);
+
+        let mut expected = vec![];
+        for (i, (elf, range)) in elves.iter().enumerate() {
+            for (other_elf, other) in &elves[i + 1..] {
+                // the reversed 5-3 assignment is empty and overlaps nothing
+                if range.overlaps(other) && !range.is_empty() && !other.is_empty() {
+                    expected.push((*elf, *other_elf));
+                }
+            }
+        }
+        expected.sort();
+        assert_eq!(expected, overlapping_pairs(&elves));
+    }
+}